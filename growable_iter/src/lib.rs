@@ -32,10 +32,10 @@ impl<T, I: Iterator<Item = T>, F, B: PushBack<T>> PushBack<T> for GrowableIterat
     }
 }
 
-impl<T, I: Iterator<Item = T>, F: PushBack<T, Err = Infallible> + PeekFront<T>, B> PeekFront<T>
+impl<T, I: Iterator<Item = T>, F: PushBack<T, Err = Infallible> + PeekFront<T>, B> PullPeekFront<T>
     for GrowableIterator<I, F, B>
 {
-    fn peek_front(&mut self) -> Option<&T> {
+    fn pull_peek_front(&mut self) -> Option<&T> {
         if self.front.peek_front().is_none() {
             let item = self.iter.next()?;
             let _ = self.front.push_back(item);
@@ -44,12 +44,12 @@ impl<T, I: Iterator<Item = T>, F: PushBack<T, Err = Infallible> + PeekFront<T>,
     }
 }
 
-impl<T, I, F, B> PeekBack<T> for GrowableIterator<I, F, B>
+impl<T, I, F, B> PullPeekBack<T> for GrowableIterator<I, F, B>
 where
     I: DoubleEndedIterator<Item = T> + FusedIterator<Item = T>,
     B: PushFront<T, Err = Infallible> + PeekBack<T>,
 {
-    fn peek_back(&mut self) -> Option<&T> {
+    fn pull_peek_back(&mut self) -> Option<&T> {
         if self.back.peek_back().is_none() {
             let item = self.iter.next_back()?;
             let _ = self.back.push_front(item);
@@ -0,0 +1,75 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use infinite_iterators::sequences::arithmetic;
+use infinite_iterators::{CycleExact, InfiniteIterator};
+
+/// Hides the fast path of the wrapped iterator, to compare against the default `advance_by`
+struct Stepwise<I>(I);
+
+impl<I: InfiniteIterator> InfiniteIterator for Stepwise<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> I::Item {
+        self.0.next()
+    }
+}
+
+const N: usize = 1_000_000;
+
+fn cycle(c: &mut Criterion) {
+    let data = [1u32, 2, 3, 4, 5, 6, 7];
+    let mut group = c.benchmark_group("cycle_exact nth");
+    group.bench_function("stepwise", |b| {
+        b.iter(|| Stepwise(data.iter().cycle_exact()).nth(black_box(N)))
+    });
+    group.bench_function("fast", |b| {
+        b.iter(|| data.iter().cycle_exact().nth(black_box(N)))
+    });
+    group.finish();
+}
+
+fn arithmetic_sequence(c: &mut Criterion) {
+    let mut group = c.benchmark_group("arithmetic nth");
+    group.bench_function("stepwise", |b| {
+        b.iter(|| Stepwise(arithmetic(0u64, 3)).nth(black_box(N)))
+    });
+    group.bench_function("fast", |b| b.iter(|| arithmetic(0u64, 3).nth(black_box(N))));
+    group.finish();
+}
+
+fn step_by(c: &mut Criterion) {
+    let mut group = c.benchmark_group("step_by(1000) over arithmetic, 100 items");
+    group.bench_function("stepwise", |b| {
+        b.iter(|| {
+            let mut x = Stepwise(arithmetic(0u64, 1)).step_by(black_box(1000));
+            (0..100).map(|_| x.next()).sum::<u64>()
+        })
+    });
+    group.bench_function("fast", |b| {
+        b.iter(|| {
+            let mut x = arithmetic(0u64, 1).step_by(black_box(1000));
+            (0..100).map(|_| x.next()).sum::<u64>()
+        })
+    });
+    group.finish();
+}
+
+fn step_by_cycle(c: &mut Criterion) {
+    let data = [1u32, 2, 3, 4, 5, 6, 7];
+    let mut group = c.benchmark_group("step_by(1000) over cycle, 100 items");
+    group.bench_function("stepwise", |b| {
+        b.iter(|| {
+            let mut x = Stepwise(data.iter().cycle()).step_by(black_box(1000));
+            (0..100).map(|_| *x.next()).sum::<u32>()
+        })
+    });
+    group.bench_function("fast", |b| {
+        b.iter(|| {
+            let mut x = InfiniteIterator::step_by(data.iter().cycle(), black_box(1000));
+            (0..100).map(|_| *x.next()).sum::<u32>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, cycle, arithmetic_sequence, step_by, step_by_cycle);
+criterion_main!(benches);
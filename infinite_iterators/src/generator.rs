@@ -0,0 +1,160 @@
+/// Defines an infinite iterator as a loop with `yield` statements
+///
+/// The state variables are declared with their types and initial values,
+/// followed by `loop { ... }`.
+/// Inside the loop, every top-level `yield expr;` returns an item,
+/// and the next call to `next` continues right after it.
+///
+/// ```
+/// use infinite_iterators::{infinite_gen, InfiniteIterator};
+///
+/// let mut fib = infinite_gen! {
+///     state a: u64 = 0, b: u64 = 1;
+///     loop {
+///         yield a;
+///         let next = a + b;
+///         a = b;
+///         b = next;
+///     }
+/// };
+/// assert_eq!((fib.next(), fib.next(), fib.next(), fib.next()), (0, 1, 1, 2));
+/// ```
+///
+/// The item type is inferred from the `yield`ed expressions.
+/// Where that isn't enough (e.g. for `yield Vec::new();`),
+/// it can be given explicitly as `loop -> ItemType { ... }`.
+///
+/// This expands to a local struct holding the state variables, which is driven by
+/// [`unfold`](fn.unfold.html) together with the current position in the loop:
+/// every call to `next` runs the loop body up to the next `yield`. Because of that:
+/// - only the state variables keep their values across a `yield`,
+///   `let` bindings in the loop body don't
+/// - `yield` can't be nested in other blocks (like `if`) inside the loop
+/// - `break` and `continue` aren't supported
+/// - the types can't refer to generic parameters of the surrounding function
+/// - other variables used in the loop body are moved into the iterator
+///
+/// The body is split one statement at a time, so the macro recurses about once per statement
+/// (and once per token in the conditions of `if`, `while`, `for` and `match`).
+/// Loop bodies with more than about a hundred statements need a higher limit,
+/// e.g. `#![recursion_limit = "256"]` at the crate root.
+///
+/// If the loop body panics, every later call to `next` panics as well.
+#[macro_export]
+macro_rules! infinite_gen {
+    (state $($name:ident: $ty:ty = $init:expr),* $(,)?; loop $(-> $item:ty)? { $($body:tt)* }) => {
+        $crate::infinite_gen!(
+            @split
+            ([$($name: $ty = $init),*] {$($name),*} {$(mut $name),*})
+            [$($item)?] [] [] $($body)*
+        )
+    };
+
+    // the loop body is split into the code before every `yield`, the `yield`ed expressions
+    // and the code after the last `yield`, taking whole statements where possible
+    (@split $state:tt $item:tt [] $tail:tt) => {
+        ::core::compile_error!("infinite_gen: the loop needs at least one top-level `yield`")
+    };
+    (@split
+        ([$($name:ident: $ty:ty = $init:expr),*] $fields:tt $mut_fields:tt)
+        [$($item:ty)?]
+        [$({[$($segment:tt)*] $y:expr})*]
+        [$($tail:tt)*]
+    ) => {{
+        struct State {
+            $($name: $ty,)*
+        }
+
+        // the state is `None` only while the loop body runs,
+        // the stage is the number of `yield`s already passed in the current round
+        let init = (::core::option::Option::Some(State { $($name: $init),* }), 0usize);
+        let step = move |generator: &mut (::core::option::Option<State>, usize)| $(-> $item)? {
+            let (state, stage) = generator;
+            #[allow(unused_mut)]
+            let State $mut_fields = state
+                .take()
+                .expect("infinite_gen: the generator panicked before");
+            loop {
+                // the number of the `yield` that ends the next segment, starting at 1
+                let mut index = 0;
+                $(
+                    index += 1;
+                    if *stage + 1 == index {
+                        $($segment)*
+                        *stage = index;
+                        let item = $y;
+                        *state = ::core::option::Option::Some(State $fields);
+                        return item;
+                    }
+                )*
+                $($tail)*
+                *stage = 0;
+            }
+        };
+        $crate::unfold(init, step)
+    }};
+    (@split $state:tt $item:tt [$($segments:tt)*] [$($current:tt)*] yield $y:expr; $($rest:tt)*) => {
+        $crate::infinite_gen!(@split $state $item [$($segments)* {[$($current)*] $y}] [] $($rest)*)
+    };
+    (@split $state:tt $item:tt $segments:tt $current:tt let $($rest:tt)*) => {
+        $crate::infinite_gen!(@let $state $item $segments $current let $($rest)*)
+    };
+    // block-like statements are copied up to their (last) block,
+    // since they don't need a `;` and could be mistaken for the start of a longer expression
+    (@split $state:tt $item:tt $segments:tt $current:tt if $($rest:tt)*) => {
+        $crate::infinite_gen!(@block $state $item $segments $current [if] $($rest)*)
+    };
+    (@split $state:tt $item:tt $segments:tt $current:tt match $($rest:tt)*) => {
+        $crate::infinite_gen!(@block $state $item $segments $current [match] $($rest)*)
+    };
+    (@split $state:tt $item:tt $segments:tt $current:tt while $($rest:tt)*) => {
+        $crate::infinite_gen!(@block $state $item $segments $current [while] $($rest)*)
+    };
+    (@split $state:tt $item:tt $segments:tt $current:tt for $($rest:tt)*) => {
+        $crate::infinite_gen!(@block $state $item $segments $current [for] $($rest)*)
+    };
+    (@split $state:tt $item:tt $segments:tt $current:tt loop $($rest:tt)*) => {
+        $crate::infinite_gen!(@block $state $item $segments $current [loop] $($rest)*)
+    };
+    (@split $state:tt $item:tt $segments:tt $current:tt unsafe $($rest:tt)*) => {
+        $crate::infinite_gen!(@block $state $item $segments $current [unsafe] $($rest)*)
+    };
+    (@split $state:tt $item:tt $segments:tt [$($current:tt)*] {$($b:tt)*} $($rest:tt)*) => {
+        $crate::infinite_gen!(@split $state $item $segments [$($current)* {$($b)*}] $($rest)*)
+    };
+    (@split $state:tt $item:tt $segments:tt [$($current:tt)*] $e:expr; $($rest:tt)*) => {
+        $crate::infinite_gen!(@split $state $item $segments [$($current)* $e;] $($rest)*)
+    };
+    // anything else (like an item or a macro call with braces) is copied token by token
+    (@split $state:tt $item:tt $segments:tt [$($current:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::infinite_gen!(@raw $state $item $segments [$($current)* $t] $($rest)*)
+    };
+
+    // a `stmt` fragment keeps the `;` of a `let` (but not of an expression statement)
+    (@let $state:tt $item:tt $segments:tt [$($current:tt)*] $s:stmt; $($rest:tt)*) => {
+        $crate::infinite_gen!(@split $state $item $segments [$($current)* $s] $($rest)*)
+    };
+
+    (@block $state:tt $item:tt $segments:tt $current:tt [$($stmt:tt)*] {$($b:tt)*} else $($rest:tt)*) => {
+        $crate::infinite_gen!(@block $state $item $segments $current [$($stmt)* {$($b)*} else] $($rest)*)
+    };
+    (@block $state:tt $item:tt $segments:tt [$($current:tt)*] [$($stmt:tt)*] {$($b:tt)*} $($rest:tt)*) => {
+        $crate::infinite_gen!(@split $state $item $segments [$($current)* $($stmt)* {$($b)*}] $($rest)*)
+    };
+    (@block $state:tt $item:tt $segments:tt $current:tt [$($stmt:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::infinite_gen!(@block $state $item $segments $current [$($stmt)* $t] $($rest)*)
+    };
+
+    (@raw $state:tt $item:tt $segments:tt [$($current:tt)*] ; $($rest:tt)*) => {
+        $crate::infinite_gen!(@split $state $item $segments [$($current)* ;] $($rest)*)
+    };
+    (@raw $state:tt $item:tt $segments:tt $current:tt yield $($rest:tt)*) => {
+        $crate::infinite_gen!(@split $state $item $segments $current yield $($rest)*)
+    };
+    (@raw $state:tt $item:tt $segments:tt [$($current:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::infinite_gen!(@raw $state $item $segments [$($current)* $t] $($rest)*)
+    };
+    (@raw $state:tt $item:tt $segments:tt $current:tt) => {
+        $crate::infinite_gen!(@split $state $item $segments $current)
+    };
+}
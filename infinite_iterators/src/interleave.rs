@@ -0,0 +1,153 @@
+//! Combining any number of infinite iterators into one
+//!
+//! For two iterators, see also [`InfiniteIterator::interleave`] and [`InfiniteIterator::merge_by`].
+
+use super::InfiniteIterator;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Takes one item from every iterator in turn
+///
+/// `iters` can be an array or (with the `alloc` feature) a `Vec`,
+/// e.g. of `Box<dyn InfiniteIterator<Item = T>>` to mix different iterator types.
+///
+/// # Panics
+/// If `iters` is empty
+pub fn round_robin<S: AsRef<[I]>, I>(iters: S) -> RoundRobin<S> {
+    assert!(!iters.as_ref().is_empty(), "round_robin: no iterators");
+    RoundRobin { iters, pos: 0 }
+}
+
+#[derive(Debug, Clone)]
+pub struct RoundRobin<S> {
+    iters: S,
+    /// The index of the iterator to take the next item from
+    pos: usize,
+}
+
+impl<S> RoundRobin<S> {
+    fn next_from<I: InfiniteIterator>(iters: &mut [I], pos: &mut usize) -> I::Item {
+        let x = iters[*pos].next();
+        *pos = (*pos + 1) % iters.len();
+        x
+    }
+}
+
+impl<I: InfiniteIterator, const N: usize> InfiniteIterator for RoundRobin<[I; N]> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> I::Item {
+        Self::next_from(&mut self.iters, &mut self.pos)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I: InfiniteIterator> InfiniteIterator for RoundRobin<Vec<I>> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> I::Item {
+        Self::next_from(&mut self.iters, &mut self.pos)
+    }
+}
+
+/// Takes `weight` items from every `(iterator, weight)` pair in turn
+///
+/// Iterators with a weight of `0` are skipped.
+///
+/// # Panics
+/// If all weights are `0` (or there are no iterators at all)
+pub fn weighted_round_robin<S: AsRef<[(I, usize)]>, I>(iters: S) -> WeightedRoundRobin<S> {
+    let first = iters.as_ref().iter().position(|(_, weight)| *weight > 0);
+    let pos = first.expect("weighted_round_robin: all weights are 0");
+    let left = iters.as_ref()[pos].1;
+    WeightedRoundRobin { iters, pos, left }
+}
+
+#[derive(Debug, Clone)]
+pub struct WeightedRoundRobin<S> {
+    iters: S,
+    /// The index of the iterator to take the next item from
+    pos: usize,
+    /// How many more items to take from it, always positive
+    left: usize,
+}
+
+impl<S> WeightedRoundRobin<S> {
+    fn next_from<I: InfiniteIterator>(
+        iters: &mut [(I, usize)],
+        pos: &mut usize,
+        left: &mut usize,
+    ) -> I::Item {
+        let x = iters[*pos].0.next();
+        *left -= 1;
+        while *left == 0 {
+            *pos = (*pos + 1) % iters.len();
+            *left = iters[*pos].1;
+        }
+        x
+    }
+}
+
+impl<I: InfiniteIterator, const N: usize> InfiniteIterator for WeightedRoundRobin<[(I, usize); N]> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> I::Item {
+        Self::next_from(&mut self.iters, &mut self.pos, &mut self.left)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I: InfiniteIterator> InfiniteIterator for WeightedRoundRobin<Vec<(I, usize)>> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> I::Item {
+        Self::next_from(&mut self.iters, &mut self.pos, &mut self.left)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sequences::arithmetic;
+
+    fn take<I: InfiniteIterator>(mut i: I, n: usize) -> Vec<I::Item> {
+        (0..n).map(|_| i.next()).collect()
+    }
+
+    #[test]
+    fn round_robin_array() {
+        let x = round_robin([arithmetic(0, 3), arithmetic(1, 3), arithmetic(2, 3)]);
+        assert_eq!(take(x, 7), [0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn round_robin_boxed() {
+        let iters: Vec<Box<dyn InfiniteIterator<Item = u32>>> =
+            vec![Box::new(arithmetic(0, 1)), Box::new(crate::from_fn(|| 100))];
+        assert_eq!(take(round_robin(iters), 5), [0, 100, 1, 100, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn round_robin_empty() {
+        round_robin::<_, crate::FromFn<fn() -> u8>>([]);
+    }
+
+    #[test]
+    fn weighted() {
+        let x = weighted_round_robin([
+            (arithmetic(0, 1), 2),
+            (arithmetic(100, 1), 0),
+            (arithmetic(10, 1), 1),
+        ]);
+        assert_eq!(take(x, 7), [0, 1, 10, 2, 3, 11, 4]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn weighted_vec() {
+        let y = weighted_round_robin(vec![(arithmetic(0, 1), 0), (arithmetic(10, 1), 3)]);
+        assert_eq!(take(y, 4), [10, 11, 12, 13]);
+    }
+}
@@ -0,0 +1,806 @@
+//! The structs returned by the methods of [`InfiniteIterator`](../trait.InfiniteIterator.html)
+
+use super::{FusedIterator, InfiniteIterator};
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+use core::fmt;
+use core::iter::Fuse;
+
+#[derive(Debug, Clone)]
+pub struct Chain<A, B> {
+    pub(super) left: Option<A>,
+    pub(super) right: B,
+}
+
+impl<T, A: Iterator<Item = T> + FusedIterator, B: InfiniteIterator<Item = T>> InfiniteIterator
+    for Chain<A, B>
+{
+    type Item = T;
+
+    fn next(&mut self) -> Self::Item {
+        match self.left.as_mut().map(|iter| iter.next()) {
+            Some(Some(t)) => return t,
+            Some(None) => self.left = None,
+            None => (),
+        }
+        self.right.next()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StepBy<I> {
+    pub(super) iter: I,
+    pub(super) step: usize,
+    pub(super) first_take: bool,
+}
+
+impl<I: InfiniteIterator> StepBy<I> {
+    pub(super) fn new(iter: I, step: usize) -> Self {
+        assert_ne!(step, 0);
+        Self {
+            iter,
+            step: step - 1,
+            first_take: true,
+        }
+    }
+}
+
+impl<I: InfiniteIterator> InfiniteIterator for StepBy<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> I::Item {
+        if self.first_take {
+            self.first_take = false;
+            self.iter.next()
+        } else {
+            self.iter.nth(self.step)
+        }
+    }
+
+    fn advance_by(&mut self, n: usize) {
+        // skipping `n` items means skipping `n` whole steps of the inner iterator,
+        // no matter if the first item was taken already
+        let step = self.step + 1;
+        match n.checked_mul(step) {
+            Some(m) => self.iter.advance_by(m),
+            None => {
+                for _ in 0..n {
+                    self.iter.advance_by(step);
+                }
+            }
+        }
+    }
+}
+
+/// Its `size_hint` is always exact, which is what `TrustedLen` would promise
+#[derive(Debug, Clone)]
+pub struct Take<I> {
+    pub(super) iter: I,
+    pub(super) n: usize,
+}
+
+impl<I: InfiniteIterator> Iterator for Take<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.n == 0 {
+            None
+        } else {
+            self.n -= 1;
+            Option::Some(self.iter.next())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.n, Some(self.n))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.n {
+            // like `core::iter::Take`, this still consumes the remaining items
+            self.iter.advance_by(core::mem::take(&mut self.n));
+            None
+        } else {
+            self.n -= n + 1;
+            Some(self.iter.nth(n))
+        }
+    }
+}
+
+impl<I: InfiniteIterator> ExactSizeIterator for Take<I> {}
+
+impl<I: InfiniteIterator> FusedIterator for Take<I> {}
+
+/// A struct that converts between `Iterator`
+/// and `InfiniteIterator` both ways
+#[derive(Debug, Clone)]
+pub struct Inf<I> {
+    pub(super) iter: I,
+}
+
+impl<I: InfiniteIterator> Iterator for Inf<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.iter.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        Some(self.iter.nth(n))
+    }
+}
+
+impl<I: InfiniteIterator> FusedIterator for Inf<I> {}
+
+impl<I: Iterator> InfiniteIterator for Inf<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Self::Item {
+        self.iter.next().expect("ITerator inside Inf<I> ended")
+    }
+
+    fn advance_by(&mut self, n: usize) {
+        if n > 0 {
+            self.iter.nth(n - 1).expect("ITerator inside Inf<I> ended");
+        }
+    }
+}
+
+/// Like `core::iter::Cycle`, but skips whole rounds in constant time
+#[derive(Debug, Clone)]
+pub struct ExactCycle<I> {
+    pub(super) orig: I,
+    pub(super) iter: I,
+}
+
+impl<I: ExactSizeIterator + Clone> InfiniteIterator for ExactCycle<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> I::Item {
+        match self.iter.next() {
+            Some(x) => x,
+            None => {
+                self.iter = self.orig.clone();
+                self.iter.next().expect("ExactCycle: empty iterator")
+            }
+        }
+    }
+
+    fn advance_by(&mut self, mut n: usize) {
+        let left = self.iter.len();
+        if n >= left {
+            n = (n - left) % self.orig.len();
+            self.iter = self.orig.clone();
+        }
+        if n > 0 {
+            self.iter.nth(n - 1);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Zip<A, B> {
+    pub(super) a: A,
+    pub(super) b: B,
+}
+
+impl<A: InfiniteIterator, B: InfiniteIterator> InfiniteIterator for Zip<A, B> {
+    type Item = (A::Item, B::Item);
+
+    fn next(&mut self) -> Self::Item {
+        (self.a.next(), self.b.next())
+    }
+
+    fn advance_by(&mut self, n: usize) {
+        self.a.advance_by(n);
+        self.b.advance_by(n);
+    }
+}
+
+/// This is a regular (finite) `Iterator`, since it ends with the finite iterator `A`
+#[derive(Debug, Clone)]
+pub struct ZipWithInfinite<A, B> {
+    pub(super) a: A,
+    pub(super) b: B,
+}
+
+impl<A: Iterator, B: InfiniteIterator> Iterator for ZipWithInfinite<A, B> {
+    type Item = (A::Item, B::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let a = self.a.next()?;
+        Some((a, self.b.next()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.a.size_hint()
+    }
+}
+
+impl<A: ExactSizeIterator, B: InfiniteIterator> ExactSizeIterator for ZipWithInfinite<A, B> {}
+
+impl<A: FusedIterator, B: InfiniteIterator> FusedIterator for ZipWithInfinite<A, B> {}
+
+/// This is a regular (finite) `Iterator`, since it ends with the finite iterator `B`
+#[derive(Debug, Clone)]
+pub struct ZipWithFinite<A, B> {
+    pub(super) a: A,
+    pub(super) b: B,
+}
+
+impl<A: InfiniteIterator, B: Iterator> Iterator for ZipWithFinite<A, B> {
+    type Item = (A::Item, B::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // `b` goes first so that no item of `a` is lost once `b` is done
+        let b = self.b.next()?;
+        Some((self.a.next(), b))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.b.size_hint()
+    }
+}
+
+impl<A: InfiniteIterator, B: ExactSizeIterator> ExactSizeIterator for ZipWithFinite<A, B> {}
+
+impl<A: InfiniteIterator, B: FusedIterator> FusedIterator for ZipWithFinite<A, B> {}
+
+#[derive(Debug, Clone)]
+pub struct Pad<I: Iterator> {
+    pub(super) iter: Fuse<I>,
+    pub(super) fill: I::Item,
+}
+
+impl<I: Iterator> InfiniteIterator for Pad<I>
+where
+    I::Item: Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> I::Item {
+        match self.iter.next() {
+            Some(x) => x,
+            None => self.fill.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PadWith<I, F> {
+    pub(super) iter: Fuse<I>,
+    pub(super) f: F,
+}
+
+impl<I: Iterator, F: FnMut() -> I::Item> InfiniteIterator for PadWith<I, F> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> I::Item {
+        match self.iter.next() {
+            Some(x) => x,
+            None => (self.f)(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RepeatLast<I: Iterator> {
+    pub(super) iter: Fuse<I>,
+    /// The next item to return
+    pub(super) last: I::Item,
+}
+
+impl<I: Iterator> InfiniteIterator for RepeatLast<I>
+where
+    I::Item: Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> I::Item {
+        match self.iter.next() {
+            Some(x) => core::mem::replace(&mut self.last, x),
+            None => self.last.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Map<I, F> {
+    pub(super) iter: I,
+    pub(super) f: F,
+}
+
+impl<B, I: InfiniteIterator, F: FnMut(I::Item) -> B> InfiniteIterator for Map<I, F> {
+    type Item = B;
+
+    fn next(&mut self) -> B {
+        (self.f)(self.iter.next())
+    }
+}
+
+/// Note that this never returns if no more items match the predicate
+#[derive(Debug, Clone)]
+pub struct Filter<I, P> {
+    pub(super) iter: I,
+    pub(super) predicate: P,
+}
+
+impl<I: InfiniteIterator, P: FnMut(&I::Item) -> bool> InfiniteIterator for Filter<I, P> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> I::Item {
+        loop {
+            let x = self.iter.next();
+            if (self.predicate)(&x) {
+                return x;
+            }
+        }
+    }
+}
+
+/// Note that this never returns if `f` only returns `None` from some point on
+#[derive(Debug, Clone)]
+pub struct FilterMap<I, F> {
+    pub(super) iter: I,
+    pub(super) f: F,
+}
+
+impl<B, I: InfiniteIterator, F: FnMut(I::Item) -> Option<B>> InfiniteIterator for FilterMap<I, F> {
+    type Item = B;
+
+    fn next(&mut self) -> B {
+        loop {
+            if let Some(b) = (self.f)(self.iter.next()) {
+                return b;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Enumerate<I> {
+    pub(super) iter: I,
+    pub(super) count: usize,
+}
+
+impl<I: InfiniteIterator> InfiniteIterator for Enumerate<I> {
+    type Item = (usize, I::Item);
+
+    fn next(&mut self) -> Self::Item {
+        let i = self.count;
+        self.count += 1;
+        (i, self.iter.next())
+    }
+
+    fn advance_by(&mut self, n: usize) {
+        self.iter.advance_by(n);
+        self.count += n;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InfinitePeekable<I: InfiniteIterator> {
+    pub(super) iter: I,
+    pub(super) peeked: Option<I::Item>,
+    /// The items after `peeked`, only ever non-empty if `peeked` is `Some`
+    #[cfg(feature = "alloc")]
+    pub(super) lookahead: VecDeque<I::Item>,
+}
+
+impl<I: InfiniteIterator> InfinitePeekable<I> {
+    /// Returns a reference to the next item without advancing the iterator
+    pub fn peek(&mut self) -> &I::Item {
+        let iter = &mut self.iter;
+        self.peeked.get_or_insert_with(|| iter.next())
+    }
+
+    /// Returns a mutable reference to the next item without advancing the iterator
+    pub fn peek_mut(&mut self) -> &mut I::Item {
+        let iter = &mut self.iter;
+        self.peeked.get_or_insert_with(|| iter.next())
+    }
+
+    /// Returns a reference to the `n`th next item without advancing the iterator,
+    /// so `peek_nth(0)` is the same as `peek()`
+    #[cfg(feature = "alloc")]
+    pub fn peek_nth(&mut self, n: usize) -> &I::Item {
+        self.peek_nth_mut(n)
+    }
+
+    /// Returns a mutable reference to the `n`th next item without advancing the iterator
+    #[cfg(feature = "alloc")]
+    pub fn peek_nth_mut(&mut self, n: usize) -> &mut I::Item {
+        if n == 0 {
+            return self.peek_mut();
+        }
+        self.peek();
+        while self.lookahead.len() < n {
+            self.lookahead.push_back(self.iter.next());
+        }
+        &mut self.lookahead[n - 1]
+    }
+
+    /// Returns the next item if it satisfies `func`, otherwise leaves it in place
+    pub fn next_if(&mut self, func: impl FnOnce(&I::Item) -> bool) -> Option<I::Item> {
+        if func(self.peek()) {
+            Some(self.next())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the next item if it is equal to `expected`, otherwise leaves it in place
+    pub fn next_if_eq<T: ?Sized>(&mut self, expected: &T) -> Option<I::Item>
+    where
+        I::Item: PartialEq<T>,
+    {
+        self.next_if(|x| x == expected)
+    }
+}
+
+impl<I: InfiniteIterator> InfiniteIterator for InfinitePeekable<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> I::Item {
+        match self.peeked.take() {
+            Some(x) => {
+                #[cfg(feature = "alloc")]
+                {
+                    self.peeked = self.lookahead.pop_front();
+                }
+                x
+            }
+            None => self.iter.next(),
+        }
+    }
+
+    fn advance_by(&mut self, mut n: usize) {
+        while n > 0 && self.peeked.is_some() {
+            self.next();
+            n -= 1;
+        }
+        self.iter.advance_by(n);
+    }
+}
+
+/// Note that this never returns if all items match the predicate
+#[derive(Debug, Clone)]
+pub struct SkipWhile<I, P> {
+    pub(super) iter: I,
+    pub(super) predicate: Option<P>,
+}
+
+impl<I: InfiniteIterator, P: FnMut(&I::Item) -> bool> InfiniteIterator for SkipWhile<I, P> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> I::Item {
+        let p = match &mut self.predicate {
+            Some(p) => p,
+            None => return self.iter.next(),
+        };
+        loop {
+            let x = self.iter.next();
+            if !p(&x) {
+                self.predicate = None;
+                return x;
+            }
+        }
+    }
+}
+
+/// This is a regular (finite) `Iterator`, since it ends at the first item not matching
+#[derive(Debug, Clone)]
+pub struct TakeWhile<I, P> {
+    pub(super) iter: I,
+    pub(super) predicate: Option<P>,
+}
+
+impl<I: InfiniteIterator, P: FnMut(&I::Item) -> bool> Iterator for TakeWhile<I, P> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let p = self.predicate.as_mut()?;
+        let x = self.iter.next();
+        if p(&x) {
+            Some(x)
+        } else {
+            self.predicate = None;
+            None
+        }
+    }
+}
+
+impl<I: InfiniteIterator, P: FnMut(&I::Item) -> bool> FusedIterator for TakeWhile<I, P> {}
+
+#[derive(Debug, Clone)]
+pub struct Skip<I> {
+    pub(super) iter: I,
+    pub(super) n: usize,
+}
+
+impl<I: InfiniteIterator> InfiniteIterator for Skip<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> I::Item {
+        if self.n == 0 {
+            self.iter.next()
+        } else {
+            let n = core::mem::take(&mut self.n);
+            self.iter.nth(n)
+        }
+    }
+
+    fn advance_by(&mut self, n: usize) {
+        let skip = core::mem::take(&mut self.n);
+        self.iter.advance_by(skip);
+        self.iter.advance_by(n);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Interleave<A, B> {
+    pub(super) a: A,
+    pub(super) b: B,
+    pub(super) next_is_a: bool,
+}
+
+impl<A: InfiniteIterator, B: InfiniteIterator<Item = A::Item>> InfiniteIterator
+    for Interleave<A, B>
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> A::Item {
+        self.next_is_a = !self.next_is_a;
+        if self.next_is_a {
+            self.b.next()
+        } else {
+            self.a.next()
+        }
+    }
+}
+
+pub struct MergeBy<A: InfiniteIterator, B: InfiniteIterator, F> {
+    pub(super) a: InfinitePeekable<A>,
+    pub(super) b: InfinitePeekable<B>,
+    pub(super) is_first: F,
+}
+
+// derived impls would only require `A: Clone`, not `A::Item: Clone` for the peeked items
+impl<A: InfiniteIterator + Clone, B: InfiniteIterator + Clone, F: Clone> Clone for MergeBy<A, B, F>
+where
+    A::Item: Clone,
+    B::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        MergeBy {
+            a: self.a.clone(),
+            b: self.b.clone(),
+            is_first: self.is_first.clone(),
+        }
+    }
+}
+
+impl<A: InfiniteIterator + fmt::Debug, B: InfiniteIterator + fmt::Debug, F> fmt::Debug
+    for MergeBy<A, B, F>
+where
+    A::Item: fmt::Debug,
+    B::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MergeBy")
+            .field("a", &self.a)
+            .field("b", &self.b)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A, B, F> InfiniteIterator for MergeBy<A, B, F>
+where
+    A: InfiniteIterator,
+    B: InfiniteIterator<Item = A::Item>,
+    F: FnMut(&A::Item, &A::Item) -> bool,
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> A::Item {
+        if (self.is_first)(self.a.peek(), self.b.peek()) {
+            self.a.next()
+        } else {
+            self.b.next()
+        }
+    }
+}
+
+pub type Merge<A, B> =
+    MergeBy<A, B, fn(&<A as InfiniteIterator>::Item, &<A as InfiniteIterator>::Item) -> bool>;
+
+#[derive(Debug, Clone)]
+pub struct ArrayChunks<I, const N: usize> {
+    pub(super) iter: I,
+}
+
+impl<I: InfiniteIterator, const N: usize> InfiniteIterator for ArrayChunks<I, N> {
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Self::Item {
+        core::array::from_fn(|_| self.iter.next())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Windows<I: InfiniteIterator, const N: usize> {
+    pub(super) iter: I,
+    /// The last window returned, `None` before the first one
+    pub(super) window: Option<[I::Item; N]>,
+}
+
+impl<I: InfiniteIterator, const N: usize> InfiniteIterator for Windows<I, N>
+where
+    I::Item: Clone,
+{
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Self::Item {
+        match &mut self.window {
+            Some(window) => {
+                if N > 0 {
+                    // moves the oldest item to the end and overwrites it
+                    window.rotate_left(1);
+                    window[N - 1] = self.iter.next();
+                }
+                window.clone()
+            }
+            None => {
+                let iter = &mut self.iter;
+                self.window
+                    .get_or_insert_with(|| core::array::from_fn(|_| iter.next()))
+                    .clone()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Scan<I, St, F> {
+    pub(super) iter: I,
+    pub(super) state: St,
+    pub(super) f: F,
+}
+
+impl<B, I: InfiniteIterator, St, F: FnMut(&mut St, I::Item) -> B> InfiniteIterator
+    for Scan<I, St, F>
+{
+    type Item = B;
+
+    fn next(&mut self) -> B {
+        (self.f)(&mut self.state, self.iter.next())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Inspect<I, F> {
+    pub(super) iter: I,
+    pub(super) f: F,
+}
+
+impl<I: InfiniteIterator, F: FnMut(&I::Item)> InfiniteIterator for Inspect<I, F> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> I::Item {
+        let x = self.iter.next();
+        (self.f)(&x);
+        x
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Copied<I> {
+    pub(super) iter: I,
+}
+
+impl<'a, T: 'a + Copy, I: InfiniteIterator<Item = &'a T>> InfiniteIterator for Copied<I> {
+    type Item = T;
+
+    fn next(&mut self) -> T {
+        *self.iter.next()
+    }
+
+    fn advance_by(&mut self, n: usize) {
+        self.iter.advance_by(n)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Cloned<I> {
+    pub(super) iter: I,
+}
+
+impl<'a, T: 'a + Clone, I: InfiniteIterator<Item = &'a T>> InfiniteIterator for Cloned<I> {
+    type Item = T;
+
+    fn next(&mut self) -> T {
+        self.iter.next().clone()
+    }
+
+    fn advance_by(&mut self, n: usize) {
+        self.iter.advance_by(n)
+    }
+}
+
+/// Note that this never returns if `f` only returns empty iterators from some point on
+#[derive(Debug, Clone)]
+pub struct FlatMap<I, U: IntoIterator, F> {
+    pub(super) iter: I,
+    pub(super) f: F,
+    pub(super) inner: Option<U::IntoIter>,
+}
+
+impl<I: InfiniteIterator, U: IntoIterator, F: FnMut(I::Item) -> U> InfiniteIterator
+    for FlatMap<I, U, F>
+{
+    type Item = U::Item;
+
+    fn next(&mut self) -> U::Item {
+        loop {
+            if let Some(x) = self.inner.as_mut().and_then(Iterator::next) {
+                return x;
+            }
+            self.inner = Some((self.f)(self.iter.next()).into_iter());
+        }
+    }
+}
+
+/// Note that this never returns if only empty iterators are left
+pub struct Flatten<I: InfiniteIterator>
+where
+    I::Item: IntoIterator,
+{
+    pub(super) iter: I,
+    pub(super) inner: Option<<I::Item as IntoIterator>::IntoIter>,
+}
+
+impl<I: InfiniteIterator + Clone> Clone for Flatten<I>
+where
+    I::Item: IntoIterator,
+    <I::Item as IntoIterator>::IntoIter: Clone,
+{
+    fn clone(&self) -> Self {
+        Flatten {
+            iter: self.iter.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<I: InfiniteIterator + fmt::Debug> fmt::Debug for Flatten<I>
+where
+    I::Item: IntoIterator,
+    <I::Item as IntoIterator>::IntoIter: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Flatten")
+            .field("iter", &self.iter)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<I: InfiniteIterator> InfiniteIterator for Flatten<I>
+where
+    I::Item: IntoIterator,
+{
+    type Item = <I::Item as IntoIterator>::Item;
+
+    fn next(&mut self) -> Self::Item {
+        loop {
+            if let Some(x) = self.inner.as_mut().and_then(Iterator::next) {
+                return x;
+            }
+            self.inner = Some(self.iter.next().into_iter());
+        }
+    }
+}
@@ -1,175 +1,433 @@
 #![cfg_attr(not(test), no_std)]
 //! `infinite_iterators`: Iterators that never end
+//!
+//! The crate is `no_std`. Without the default `alloc` feature, it doesn't allocate either.
+//!
+//! # Features
+//! - `alloc` (default): the items that need an allocator, like [`memo`](memo/index.html),
+//!   [`BoxedInfinite`](type.BoxedInfinite.html), [`primes`](sequences/fn.primes.html)
+//!   and [`InfinitePeekable::peek_nth`](iters/struct.InfinitePeekable.html#method.peek_nth)
+//! - `nonempty_vec`: [`flat_map_nonempty`](trait.InfiniteIterator.html#method.flat_map_nonempty)
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 use self::iters::*;
 use core::iter::FusedIterator;
-use core::iter::{Cycle, Repeat};
+use core::iter::{Cycle, Repeat, RepeatWith};
 
 // todo: documentation
 
-pub mod iters {
-    use super::{FusedIterator, InfiniteIterator};
+#[macro_use]
+mod generator;
+pub mod interleave;
+pub mod iters;
+#[cfg(feature = "alloc")]
+pub mod memo;
+pub mod rng;
+pub mod sequences;
 
-    pub struct Chain<A, B> {
-        pub(super) left: Option<A>,
-        pub(super) right: B,
+/// Promote an iterator to an infinite iterator
+pub trait PromiseInfinite: Iterator + Sized {
+    fn promise_infinite(self) -> Inf<Self> {
+        Inf { iter: self }
+    }
+}
+
+impl<I: Iterator> PromiseInfinite for I {}
+
+pub trait ChainInfinite: Iterator + FusedIterator + Sized {
+    fn chain_infinite<I: InfiniteIterator>(self, right: I) -> Chain<Self, I> {
+        Chain {
+            left: Some(self),
+            right,
+        }
     }
+}
 
-    impl<T, A: Iterator<Item = T> + FusedIterator, B: InfiniteIterator<Item = T>> InfiniteIterator
-        for Chain<A, B>
+impl<I: Iterator + FusedIterator> ChainInfinite for I {}
+
+/// Cycle an iterator of known length, see [`ExactCycle`](iters/struct.ExactCycle.html)
+pub trait CycleExact: ExactSizeIterator + Clone {
+    /// # Panics
+    /// If `self` is empty
+    fn cycle_exact(self) -> ExactCycle<Self> {
+        assert_ne!(self.len(), 0, "cycle_exact: empty iterator");
+        ExactCycle {
+            orig: self.clone(),
+            iter: self,
+        }
+    }
+}
+
+impl<I: ExactSizeIterator + Clone> CycleExact for I {}
+
+/// Zip a finite iterator with an infinite one, which is as long as the finite one
+pub trait ZipInfinite: Iterator + Sized {
+    fn zip_infinite<I: InfiniteIterator>(self, other: I) -> ZipWithInfinite<Self, I> {
+        ZipWithInfinite { a: self, b: other }
+    }
+}
+
+impl<I: Iterator> ZipInfinite for I {}
+
+/// Turn a finite iterator into an infinite one by padding it after it ended
+pub trait PadInfinite: Iterator + Sized {
+    /// Yields `fill` forever after `self` ended
+    fn pad_infinite(self, fill: Self::Item) -> Pad<Self>
+    where
+        Self::Item: Clone,
     {
-        type Item = T;
+        Pad {
+            iter: self.fuse(),
+            fill,
+        }
+    }
 
-        fn next(&mut self) -> Self::Item {
-            match self.left.as_mut().map(|iter| iter.next()) {
-                Some(Some(t)) => return t,
-                Some(None) => self.left = None,
-                None => (),
-            }
-            self.right.next()
+    /// Yields the return values of `f` after `self` ended
+    fn pad_with<F: FnMut() -> Self::Item>(self, f: F) -> PadWith<Self, F> {
+        PadWith {
+            iter: self.fuse(),
+            f,
         }
     }
 
-    pub struct StepBy<I> {
-        pub(super) iter: I,
-        pub(super) step: usize,
-        pub(super) first_take: bool,
+    /// Yields the last item forever after `self` ended
+    ///
+    /// This eagerly takes the first item and returns `None` if there is none,
+    /// since there would be nothing to repeat.
+    fn repeat_last(mut self) -> Option<RepeatLast<Self>>
+    where
+        Self::Item: Clone,
+    {
+        let last = self.next()?;
+        Some(RepeatLast {
+            iter: self.fuse(),
+            last,
+        })
     }
+}
 
-    impl<I: InfiniteIterator> StepBy<I> {
-        pub(super) fn new(iter: I, step: usize) -> Self {
-            assert_ne!(step, 0);
-            Self {
-                iter,
-                step: step - 1,
-                first_take: true,
-            }
+impl<I: Iterator> PadInfinite for I {}
+
+/// An iterator that never ends
+pub trait InfiniteIterator {
+    type Item;
+
+    fn next(&mut self) -> Self::Item;
+
+    /// Skips the next `n` items
+    ///
+    /// The default implementation calls `next` `n` times,
+    /// iterators that can skip faster should override this.
+    fn advance_by(&mut self, n: usize) {
+        for _ in 0..n {
+            self.next();
         }
     }
 
-    impl<I: InfiniteIterator> InfiniteIterator for StepBy<I> {
-        type Item = I::Item;
+    fn nth(&mut self, n: usize) -> Self::Item {
+        self.advance_by(n);
+        self.next()
+    }
 
-        fn next(&mut self) -> I::Item {
-            if self.first_take {
-                self.first_take = false;
-                self.iter.next()
-            } else {
-                self.iter.nth(self.step)
-            }
+    fn step_by(self, step: usize) -> StepBy<Self>
+    where
+        Self: Sized,
+    {
+        StepBy::new(self, step)
+    }
+
+    fn zip<J: InfiniteIterator>(self, other: J) -> Zip<Self, J>
+    where
+        Self: Sized,
+    {
+        Zip { a: self, b: other }
+    }
+
+    /// Zip with a finite iterator, which results in a finite iterator
+    fn zip_finite<J: Iterator>(self, other: J) -> ZipWithFinite<Self, J>
+    where
+        Self: Sized,
+    {
+        ZipWithFinite { a: self, b: other }
+    }
+
+    fn map<B, F: FnMut(Self::Item) -> B>(self, f: F) -> Map<Self, F>
+    where
+        Self: Sized,
+    {
+        Map { iter: self, f }
+    }
+
+    /// Calls `f` on every item, forever
+    fn for_each<F: FnMut(Self::Item)>(mut self, mut f: F) -> !
+    where
+        Self: Sized,
+    {
+        loop {
+            f(self.next())
         }
     }
 
-    pub struct Take<I> {
-        pub(super) iter: I,
-        pub(super) n: usize,
+    /// Note that the resulting iterator never returns
+    /// if no more items match the predicate
+    fn filter<P: FnMut(&Self::Item) -> bool>(self, predicate: P) -> Filter<Self, P>
+    where
+        Self: Sized,
+    {
+        Filter {
+            iter: self,
+            predicate,
+        }
     }
 
-    impl<I: InfiniteIterator> Iterator for Take<I> {
-        type Item = I::Item;
+    /// Note that the resulting iterator never returns
+    /// if `f` only returns `None` from some point on
+    fn filter_map<B, F: FnMut(Self::Item) -> Option<B>>(self, f: F) -> FilterMap<Self, F>
+    where
+        Self: Sized,
+    {
+        FilterMap { iter: self, f }
+    }
 
-        fn next(&mut self) -> Option<Self::Item> {
-            if self.n == 0 {
-                None
-            } else {
-                self.n -= 1;
-                Option::Some(self.iter.next())
-            }
+    fn enumerate(self) -> Enumerate<Self>
+    where
+        Self: Sized,
+    {
+        Enumerate {
+            iter: self,
+            count: 0,
         }
     }
 
-    /// A struct that converts between `Iterator`
-    /// and `InfiniteIterator` both ways
-    pub struct Inf<I> {
-        pub(super) iter: I,
+    fn peekable(self) -> InfinitePeekable<Self>
+    where
+        Self: Sized,
+    {
+        InfinitePeekable {
+            iter: self,
+            peeked: None,
+            #[cfg(feature = "alloc")]
+            lookahead: alloc::collections::VecDeque::new(),
+        }
     }
 
-    impl<I: InfiniteIterator> Iterator for Inf<I> {
-        type Item = I::Item;
+    /// Note that the resulting iterator never returns
+    /// if all items match the predicate
+    fn skip_while<P: FnMut(&Self::Item) -> bool>(self, predicate: P) -> SkipWhile<Self, P>
+    where
+        Self: Sized,
+    {
+        SkipWhile {
+            iter: self,
+            predicate: Some(predicate),
+        }
+    }
 
-        fn next(&mut self) -> Option<Self::Item> {
-            Some(self.iter.next())
+    /// Returns a regular (finite) `Iterator`
+    /// that ends at the first item not matching the predicate
+    fn take_while<P: FnMut(&Self::Item) -> bool>(self, predicate: P) -> TakeWhile<Self, P>
+    where
+        Self: Sized,
+    {
+        TakeWhile {
+            iter: self,
+            predicate: Some(predicate),
         }
     }
 
-    impl<I: Iterator> InfiniteIterator for Inf<I> {
-        type Item = I::Item;
+    fn skip(self, n: usize) -> Skip<Self>
+    where
+        Self: Sized,
+    {
+        Skip { iter: self, n }
+    }
+
+    fn take(self, n: usize) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take { iter: self, n }
+    }
 
-        fn next(&mut self) -> Self::Item {
-            self.iter.next().expect("ITerator inside Inf<I> ended")
+    /// Maps every item to a finite iterator and yields the items of those
+    ///
+    /// # Livelock
+    /// If `f` only returns empty iterators from some point on,
+    /// the resulting iterator loops forever without yielding anything.
+    #[cfg_attr(
+        feature = "nonempty_vec",
+        doc = "Use [`flat_map_nonempty`](#method.flat_map_nonempty) to rule that out."
+    )]
+    fn flat_map<U: IntoIterator, F: FnMut(Self::Item) -> U>(self, f: F) -> FlatMap<Self, U, F>
+    where
+        Self: Sized,
+    {
+        FlatMap {
+            iter: self,
+            f,
+            inner: None,
         }
     }
-}
 
-/// Promote an iterator to an infinite iterator
-pub trait PromiseInfinite: Iterator + Sized {
-    fn promise_infinite(self) -> Inf<Self> {
-        Inf { iter: self }
+    /// Like [`flat_map`](#method.flat_map), but `f` has to return
+    /// a [`NonemptyIterator`](../nonempty_vec/iter/trait.NonemptyIterator.html)
+    /// (like the ones returned by `NonemptyVec::iter`),
+    /// which guarantees that every call to `next` makes progress.
+    ///
+    /// `NonemptyIterator` is sealed, so only the iterators of `nonempty_vec`,
+    /// which are known to yield at least one item, can be returned here.
+    #[cfg(feature = "nonempty_vec")]
+    fn flat_map_nonempty<U, F>(self, f: F) -> FlatMap<Self, U, F>
+    where
+        Self: Sized,
+        U: nonempty_vec::NonemptyIterator,
+        F: FnMut(Self::Item) -> U,
+    {
+        self.flat_map(f)
     }
-}
 
-impl<I: Iterator> PromiseInfinite for I {}
+    /// Flattens an iterator of finite iterators
+    ///
+    /// # Livelock
+    /// If only empty iterators are left from some point on,
+    /// the resulting iterator loops forever without yielding anything.
+    fn flatten(self) -> Flatten<Self>
+    where
+        Self: Sized,
+        Self::Item: IntoIterator,
+    {
+        Flatten {
+            iter: self,
+            inner: None,
+        }
+    }
 
-pub trait ChainInfinite: Iterator + FusedIterator + Sized {
-    fn chain_infinite<I: InfiniteIterator>(self, right: I) -> Chain<Self, I> {
-        Chain {
-            left: Some(self),
-            right,
+    /// Alternates between the items of `self` and `other`, starting with `self`
+    ///
+    /// See [`interleave`](interleave/index.html) for more than two iterators.
+    fn interleave<J: InfiniteIterator<Item = Self::Item>>(self, other: J) -> Interleave<Self, J>
+    where
+        Self: Sized,
+    {
+        Interleave {
+            a: self,
+            b: other,
+            next_is_a: true,
         }
     }
-}
 
-impl<I: Iterator + FusedIterator> ChainInfinite for I {}
+    /// Merges two infinite iterators that are sorted according to `is_first`
+    /// into one sorted infinite iterator
+    ///
+    /// `is_first(a, b)` returns whether `a` (from `self`) comes before `b` (from `other`),
+    /// on ties it should return `true` to keep the merge stable.
+    fn merge_by<J, F>(self, other: J, is_first: F) -> MergeBy<Self, J, F>
+    where
+        Self: Sized,
+        J: InfiniteIterator<Item = Self::Item>,
+        F: FnMut(&Self::Item, &Self::Item) -> bool,
+    {
+        MergeBy {
+            a: self.peekable(),
+            b: other.peekable(),
+            is_first,
+        }
+    }
 
-/// An iterator that never ends
-pub trait InfiniteIterator {
-    type Item;
+    /// [`merge_by`](#method.merge_by) using the natural order
+    fn merge<J>(self, other: J) -> Merge<Self, J>
+    where
+        Self: Sized,
+        Self::Item: Ord,
+        J: InfiniteIterator<Item = Self::Item>,
+    {
+        self.merge_by(other, |a, b| a <= b)
+    }
 
-    fn next(&mut self) -> Self::Item;
+    /// Erases the type of `self`,
+    /// e.g. to store different infinite iterators in one collection
+    #[cfg(feature = "alloc")]
+    fn into_boxed<'a>(self) -> BoxedInfinite<'a, Self::Item>
+    where
+        Self: Sized + 'a,
+    {
+        alloc::boxed::Box::new(self)
+    }
 
-    fn nth(&mut self, mut n: usize) -> Self::Item {
-        loop {
-            let x = self.next();
-            if n == 0 {
-                return x;
-            }
-            n -= 1;
+    /// Caches the items for random access, see [`Memo`](memo/struct.Memo.html)
+    #[cfg(feature = "alloc")]
+    fn memo(self) -> memo::Memo<Self>
+    where
+        Self: Sized,
+    {
+        memo::Memo::new(self)
+    }
+
+    /// Yields the items in non-overlapping arrays of `N`
+    fn array_chunks<const N: usize>(self) -> ArrayChunks<Self, N>
+    where
+        Self: Sized,
+    {
+        ArrayChunks { iter: self }
+    }
+
+    /// Yields overlapping arrays of `N` consecutive items, advancing by one item each time
+    fn windows<const N: usize>(self) -> Windows<Self, N>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        Windows {
+            iter: self,
+            window: None,
         }
     }
 
-    fn step_by(self, step: usize) -> StepBy<Self>
+    /// Like `map`, but `f` also gets mutable access to a state that is kept between items
+    fn scan<St, B, F: FnMut(&mut St, Self::Item) -> B>(
+        self,
+        initial_state: St,
+        f: F,
+    ) -> Scan<Self, St, F>
     where
         Self: Sized,
     {
-        StepBy::new(self, step)
+        Scan {
+            iter: self,
+            state: initial_state,
+            f,
+        }
     }
 
-    // todo:
-    //  - zip
-    //  - map
-    //  - for_each
-    //  - filter
-    //  - filter_map
-    //  - enumerate
-    //  - peekable
-    //  - skip_while
-    //  - take_while
-    //  - skip
+    fn inspect<F: FnMut(&Self::Item)>(self, f: F) -> Inspect<Self, F>
+    where
+        Self: Sized,
+    {
+        Inspect { iter: self, f }
+    }
 
-    fn take(self, n: usize) -> Take<Self>
+    fn by_ref(&mut self) -> &mut Self
     where
         Self: Sized,
     {
-        Take { iter: self, n }
+        self
     }
 
-    // todo:
-    //  - flat_map
-    //  - flatten
-    //  - inspect
-    //  - by_ref
-    //  - copied
-    //  - cloned
+    fn copied<'a, T: 'a + Copy>(self) -> Copied<Self>
+    where
+        Self: Sized + InfiniteIterator<Item = &'a T>,
+    {
+        Copied { iter: self }
+    }
+
+    fn cloned<'a, T: 'a + Clone>(self) -> Cloned<Self>
+    where
+        Self: Sized + InfiniteIterator<Item = &'a T>,
+    {
+        Cloned { iter: self }
+    }
 
     fn iterator(self) -> Inf<Self>
     where
@@ -179,6 +437,39 @@ pub trait InfiniteIterator {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<I: InfiniteIterator + ?Sized> InfiniteIterator for alloc::boxed::Box<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Self::Item {
+        (**self).next()
+    }
+
+    fn advance_by(&mut self, n: usize) {
+        (**self).advance_by(n)
+    }
+
+    fn nth(&mut self, n: usize) -> Self::Item {
+        (**self).nth(n)
+    }
+}
+
+impl<I: InfiniteIterator + ?Sized> InfiniteIterator for &mut I {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Self::Item {
+        (**self).next()
+    }
+
+    fn advance_by(&mut self, n: usize) {
+        (**self).advance_by(n)
+    }
+
+    fn nth(&mut self, n: usize) -> Self::Item {
+        (**self).nth(n)
+    }
+}
+
 impl<I> InfiniteIterator for Cycle<I>
 where
     Cycle<I>: Iterator,
@@ -188,6 +479,16 @@ where
     fn next(&mut self) -> Self::Item {
         Iterator::next(self).unwrap()
     }
+
+    fn advance_by(&mut self, n: usize) {
+        if n > 0 {
+            Iterator::nth(self, n - 1);
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Self::Item {
+        Iterator::nth(self, n).unwrap()
+    }
 }
 
 impl<A: Clone> InfiniteIterator for Repeat<A> {
@@ -196,8 +497,23 @@ impl<A: Clone> InfiniteIterator for Repeat<A> {
     fn next(&mut self) -> Self::Item {
         Iterator::next(self).unwrap()
     }
+
+    fn advance_by(&mut self, _n: usize) {}
 }
 
+impl<A, F: FnMut() -> A> InfiniteIterator for RepeatWith<F> {
+    type Item = A;
+
+    fn next(&mut self) -> Self::Item {
+        Iterator::next(self).unwrap()
+    }
+}
+
+/// A type-erased infinite iterator, see [`into_boxed`](trait.InfiniteIterator.html#method.into_boxed)
+#[cfg(feature = "alloc")]
+pub type BoxedInfinite<'a, T> = alloc::boxed::Box<dyn InfiniteIterator<Item = T> + 'a>;
+
+#[derive(Debug, Clone)]
 pub struct FromFn<F>(F);
 
 pub fn from_fn<T, F: FnMut() -> T>(f: F) -> FromFn<F> {
@@ -212,6 +528,7 @@ impl<T, F: FnMut() -> T> InfiniteIterator for FromFn<F> {
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct Successors<T, F> {
     t: T,
     f: F,
@@ -230,6 +547,28 @@ impl<T, F: FnMut(&T) -> T> InfiniteIterator for Successors<T, F> {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct Unfold<St, F> {
+    state: St,
+    f: F,
+}
+
+/// Calls `f` with mutable access to `state` to produce every item
+///
+/// See also [`infinite_gen!`](macro.infinite_gen.html) for more complex state machines.
+pub fn unfold<St, T, F: FnMut(&mut St) -> T>(state: St, f: F) -> Unfold<St, F> {
+    Unfold { state, f }
+}
+
+impl<St, T, F: FnMut(&mut St) -> T> InfiniteIterator for Unfold<St, F> {
+    type Item = T;
+
+    fn next(&mut self) -> Self::Item {
+        (self.f)(&mut self.state)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Decaying<T>(T);
 
 impl<T: Default> InfiniteIterator for Decaying<T> {
@@ -251,6 +590,7 @@ pub fn true_once() -> impl InfiniteIterator<Item = bool> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fmt;
     use std::iter::{once, repeat};
 
     fn inf_next<I: InfiniteIterator>(i: &mut I) -> I::Item {
@@ -277,9 +617,9 @@ mod tests {
     #[test]
     fn decaying_bool() {
         let mut x = true_once();
-        assert_eq!(x.next(), true);
-        assert_eq!(x.next(), false);
-        assert_eq!(x.next(), false);
+        assert!(x.next());
+        assert!(!x.next());
+        assert!(!x.next());
     }
 
     #[test]
@@ -289,4 +629,477 @@ mod tests {
         assert_eq!(x.next(), 0);
         assert_eq!(x.next(), 0);
     }
+
+    fn naturals() -> impl InfiniteIterator<Item = usize> {
+        successors(0, |n| n + 1)
+    }
+
+    #[test]
+    fn zip_map() {
+        let mut x = naturals().zip(repeat('a')).map(|(n, c)| (n * 2, c));
+        assert_eq!(x.next(), (0, 'a'));
+        assert_eq!(x.next(), (2, 'a'));
+    }
+
+    #[test]
+    fn for_each() {
+        let mut seen = 0;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            naturals().for_each(|n| {
+                seen = n;
+                if n == 5 {
+                    panic!("stop");
+                }
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(seen, 5);
+    }
+
+    #[test]
+    fn filter_filter_map() {
+        let mut evens = naturals().filter(|n| n % 2 == 0);
+        assert_eq!((evens.next(), evens.next(), evens.next()), (0, 2, 4));
+        let mut halves = naturals().filter_map(|n| if n % 2 == 0 { Some(n / 2) } else { None });
+        assert_eq!((halves.next(), halves.next(), halves.next()), (0, 1, 2));
+    }
+
+    #[test]
+    fn enumerate() {
+        let mut x = InfiniteIterator::enumerate(repeat('a'));
+        assert_eq!(x.next(), (0, 'a'));
+        assert_eq!(x.next(), (1, 'a'));
+    }
+
+    #[test]
+    fn peekable() {
+        let mut x = naturals().peekable();
+        assert_eq!(*x.peek(), 0);
+        assert_eq!(*x.peek(), 0);
+        assert_eq!(x.next(), 0);
+        assert_eq!(x.next(), 1);
+        assert_eq!(*x.peek(), 2);
+        *x.peek_mut() = 10;
+        assert_eq!(x.next(), 10);
+        assert_eq!(x.next_if(|n| *n % 2 == 0), None);
+        assert_eq!(x.next_if_eq(&3), Some(3));
+        assert_eq!(x.next_if_eq(&3), None);
+        assert_eq!(x.next(), 4);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn peek_nth() {
+        let mut x = naturals().peekable();
+        assert_eq!(*x.peek_nth(2), 2);
+        assert_eq!(*x.peek_nth(0), 0);
+        *x.peek_nth_mut(1) = 10;
+        assert_eq!(*x.peek_nth(4), 4);
+        assert_eq!(x.next(), 0);
+        assert_eq!(*x.peek(), 10);
+        assert_eq!(*x.peek_nth(1), 2);
+        assert_eq!(x.next_if_eq(&10), Some(10));
+        let first: Vec<_> = (0..4).map(|_| x.next()).collect();
+        assert_eq!(first, [2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn skip_take_while() {
+        let mut x = naturals().skip_while(|n| *n < 3);
+        assert_eq!((x.next(), x.next()), (3, 4));
+        let mut y = naturals().take_while(|n| *n < 3);
+        assert_eq!(y.by_ref().collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(y.next(), None);
+    }
+
+    #[test]
+    fn skip() {
+        let mut x = naturals().skip(3);
+        assert_eq!((x.next(), x.next()), (3, 4));
+    }
+
+    #[test]
+    fn inspect() {
+        let mut seen = Vec::new();
+        let mut x = naturals().inspect(|n| seen.push(*n));
+        x.next();
+        x.next();
+        drop(x);
+        assert_eq!(seen, [0, 1]);
+    }
+
+    #[test]
+    fn by_ref() {
+        let mut x = naturals();
+        assert_eq!(x.by_ref().take(2).collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(x.next(), 2);
+    }
+
+    #[test]
+    fn copied_cloned() {
+        let v = [String::from("a"), String::from("b")];
+        let mut x = InfiniteIterator::cloned(v.iter().cycle());
+        assert_eq!(
+            (inf_next(&mut x), inf_next(&mut x)),
+            ("a".into(), "b".into())
+        );
+        let mut y = InfiniteIterator::copied([1, 2].iter().cycle());
+        assert_eq!(
+            (inf_next(&mut y), inf_next(&mut y), inf_next(&mut y)),
+            (1, 2, 1)
+        );
+    }
+
+    #[test]
+    fn flat_map_flatten() {
+        let mut x = naturals().flat_map(|n| 0..n);
+        let first: Vec<_> = (0..6).map(|_| x.next()).collect();
+        assert_eq!(first, [0, 0, 1, 0, 1, 2]);
+
+        let mut y = naturals().map(|n| vec![n; n % 3]).flatten();
+        let first: Vec<_> = (0..5).map(|_| y.next()).collect();
+        assert_eq!(first, [1, 2, 2, 4, 5]);
+    }
+
+    #[cfg(feature = "nonempty_vec")]
+    #[test]
+    fn flat_map_nonempty() {
+        use nonempty_vec::NonemptyVec;
+        let mut x = naturals().flat_map_nonempty(|n| {
+            let mut v = NonemptyVec::<_>::new(n);
+            v.push(n);
+            v.into_nonempty_iter()
+        });
+        let first: Vec<_> = (0..5).map(|_| x.next()).collect();
+        assert_eq!(first, [0, 0, 1, 1, 2]);
+    }
+
+    #[test]
+    fn zip_finite_infinite() {
+        let x: Vec<_> = "abc".chars().zip_infinite(naturals()).collect();
+        assert_eq!(x, [('a', 0), ('b', 1), ('c', 2)]);
+        let y = naturals().zip_finite(vec!['a', 'b'].into_iter());
+        assert_eq!(y.len(), 2);
+        assert_eq!(y.collect::<Vec<_>>(), [(0, 'a'), (1, 'b')]);
+    }
+
+    #[test]
+    fn pad() {
+        let mut x = vec![1, 2].into_iter().pad_infinite(0);
+        let first: Vec<_> = (0..4).map(|_| x.next()).collect();
+        assert_eq!(first, [1, 2, 0, 0]);
+
+        let mut n = 10;
+        let mut y = once(1).pad_with(|| {
+            n += 1;
+            n
+        });
+        let first: Vec<_> = (0..3).map(|_| y.next()).collect();
+        assert_eq!(first, [1, 11, 12]);
+
+        let mut z = vec![1, 2].into_iter().repeat_last().unwrap();
+        let first: Vec<_> = (0..4).map(|_| z.next()).collect();
+        assert_eq!(first, [1, 2, 2, 2]);
+        assert!(std::iter::empty::<u8>().repeat_last().is_none());
+    }
+
+    #[test]
+    fn array_chunks_windows() {
+        let mut x = naturals().array_chunks::<3>();
+        assert_eq!(x.next(), [0, 1, 2]);
+        assert_eq!(x.next(), [3, 4, 5]);
+
+        let mut y = naturals().windows::<3>();
+        assert_eq!(y.next(), [0, 1, 2]);
+        assert_eq!(y.next(), [1, 2, 3]);
+        assert_eq!(y.next(), [2, 3, 4]);
+
+        let mut z = naturals().windows::<0>();
+        assert_eq!(z.next(), []);
+    }
+
+    #[test]
+    fn interleave_merge() {
+        let mut x = naturals().interleave(from_fn(|| 100));
+        let first: Vec<_> = (0..4).map(|_| x.next()).collect();
+        assert_eq!(first, [0, 100, 1, 100]);
+
+        let evens = naturals().map(|n| n * 2);
+        let threes = naturals().map(|n| n * 3);
+        let mut y = evens.merge(threes);
+        let first: Vec<_> = (0..8).map(|_| y.next()).collect();
+        assert_eq!(first, [0, 0, 2, 3, 4, 6, 6, 8]);
+
+        let mut z = naturals()
+            .map(|n| (n * 2, 'a'))
+            .merge_by(naturals().map(|n| (n * 2, 'b')), |a, b| a.0 <= b.0);
+        let first: Vec<_> = (0..4).map(|_| z.next()).collect();
+        assert_eq!(first, [(0, 'a'), (0, 'b'), (2, 'a'), (2, 'b')]);
+    }
+
+    #[test]
+    fn advance_by() {
+        let mut x = vec![1, 2, 3].into_iter().cycle_exact();
+        x.advance_by(1);
+        assert_eq!(x.next(), 2);
+        x.advance_by(3_000_000_001);
+        assert_eq!(x.nth(3), 1);
+        assert_eq!(x.next(), 2);
+
+        let mut y = InfiniteIterator::step_by(vec![1, 2, 3].into_iter().cycle_exact(), 2);
+        y.advance_by(2);
+        assert_eq!(y.next(), 2);
+        y.advance_by(1);
+        assert_eq!(y.next(), 3);
+
+        let mut z = naturals().enumerate().skip(3).peekable();
+        assert_eq!(z.peek().0, 3);
+        z.advance_by(2);
+        assert_eq!(z.next(), (5, 5));
+
+        let mut w = vec![0, 1, 2, 3].into_iter().promise_infinite();
+        InfiniteIterator::advance_by(&mut w, 2);
+        assert_eq!(inf_next(&mut w), 2);
+
+        assert_eq!(InfiniteIterator::nth(&mut repeat(7), usize::MAX), 7);
+        assert_eq!(
+            InfiniteIterator::nth(&mut vec![1, 2].into_iter().cycle(), 5),
+            2
+        );
+        let mut c = vec![1, 2, 3].into_iter().cycle();
+        InfiniteIterator::advance_by(&mut c, 4);
+        assert_eq!(inf_next(&mut c), 2);
+        InfiniteIterator::advance_by(&mut c, 0);
+        assert_eq!(inf_next(&mut c), 3);
+        let mut s = InfiniteIterator::step_by(vec![1, 2, 3].into_iter().cycle(), 4);
+        assert_eq!((s.next(), s.next(), s.next()), (1, 2, 3));
+    }
+
+    #[test]
+    fn size_hints() {
+        let x = naturals().take(5);
+        assert_eq!(x.len(), 5);
+        let mut y = naturals().take(5);
+        assert_eq!(y.nth(1), Some(1));
+        assert_eq!(y.size_hint(), (3, Some(3)));
+        assert_eq!(y.nth(3), None);
+        assert_eq!(y.len(), 0);
+        assert_eq!(y.next(), None);
+
+        let mut z = naturals().iterator();
+        assert_eq!(z.size_hint(), (usize::MAX, None));
+        assert_eq!(z.nth(2), Some(2));
+        let v: Vec<_> = z.by_ref().take(3).collect();
+        assert_eq!(v, [3, 4, 5]);
+    }
+
+    #[test]
+    fn std_impls() {
+        let mut n = 0;
+        let mut x = std::iter::repeat_with(|| {
+            n += 1;
+            n
+        });
+        assert_eq!((inf_next(&mut x), inf_next(&mut x)), (1, 2));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn boxed() {
+        let mut iters: Vec<BoxedInfinite<usize>> = vec![
+            naturals().into_boxed(),
+            sequences::count_from(10usize, sequences::Overflow::Panic).into_boxed(),
+        ];
+        let firsts: Vec<_> = iters.iter_mut().map(|i| i.next()).collect();
+        assert_eq!(firsts, [0, 10]);
+    }
+
+    #[test]
+    fn clone_debug() {
+        /// Only the start of the `Debug` output is checked,
+        /// since the rest contains the formatting of std types
+        fn check<I>(mut i: I, debug: &str)
+        where
+            I: InfiniteIterator + Clone + fmt::Debug,
+            I::Item: PartialEq + fmt::Debug,
+        {
+            assert!(format!("{:?}", i).starts_with(debug), "{:?}", i);
+            let mut j = i.clone();
+            assert_eq!(i.next(), j.next());
+            assert_eq!(i.nth(2), j.nth(2));
+        }
+
+        check(from_fn((|| 3) as fn() -> u8), "FromFn(");
+        check(decaying(5u8), "Decaying(5)");
+        let double = (|n: &u8| n * 2) as fn(&u8) -> u8;
+        check(successors(1u8, double), "Successors { t: 1, f: ");
+        check(
+            vec![1u8].into_iter().chain_infinite(repeat(2)),
+            "Chain { left: Some(",
+        );
+        check(
+            InfiniteIterator::step_by(repeat(1u8), 2),
+            "StepBy { iter: Repeat",
+        );
+        check(
+            vec![1u8].into_iter().cycle().promise_infinite(),
+            "Inf { iter: Cycle",
+        );
+
+        let take = InfiniteIterator::take(repeat(1u8), 2);
+        assert!(format!("{:?}", take).starts_with("Take { iter: Repeat"));
+        assert_eq!(take.clone().collect::<Vec<_>>(), [1, 1]);
+        assert_eq!(take.collect::<Vec<_>>(), [1, 1]);
+    }
+
+    #[test]
+    fn unfold_scan() {
+        let mut x = unfold((0, 1), |(a, b)| {
+            let next = *a + *b;
+            core::mem::replace(a, core::mem::replace(b, next))
+        });
+        let first: Vec<_> = (0..6).map(|_| x.next()).collect();
+        assert_eq!(first, [0, 1, 1, 2, 3, 5]);
+
+        let mut y = naturals().scan(0, |sum, n| {
+            *sum += n;
+            *sum
+        });
+        let first: Vec<_> = (0..5).map(|_| y.next()).collect();
+        assert_eq!(first, [0, 1, 3, 6, 10]);
+    }
+
+    #[test]
+    fn infinite_gen() {
+        // 0, 0, 1, 1, 2, 2, ... with a marker after every pair
+        let mut x = infinite_gen! {
+            state n: i32 = 0, pairs: usize = 0;
+            loop -> i32 {
+                yield n;
+                yield n;
+                n += 1;
+                pairs += 1;
+                yield -(pairs as i32);
+            }
+        };
+        let first: Vec<_> = (0..7).map(|_| x.next()).collect();
+        assert_eq!(first, [0, 0, -1, 1, 1, -2, 2]);
+
+        let start = String::from("a");
+        let mut y = infinite_gen! {
+            state s: String = start;
+            loop -> String {
+                s.push('b');
+                yield s.clone();
+            }
+        };
+        assert_eq!((y.next(), y.next()), ("ab".into(), "abb".into()));
+
+        let mut z = infinite_gen! {
+            state v: Vec<u8> = vec![];
+            loop -> Vec<u8> {
+                yield v.clone();
+                v.push(1);
+            }
+        };
+        assert_eq!((z.next(), z.next()), (vec![], vec![1]));
+    }
+
+    #[test]
+    fn infinite_gen_if_else() {
+        // FizzBuzz, with 0 for "Fizz", -1 for "Buzz" and -2 for "FizzBuzz"
+        let mut x = infinite_gen! {
+            state n: i32 = 0;
+            loop {
+                n += 1;
+                let out;
+                if n % 15 == 0 {
+                    out = -2;
+                } else if n % 5 == 0 {
+                    out = -1;
+                } else if n % 3 == 0 {
+                    out = 0;
+                } else {
+                    out = n;
+                }
+                yield out;
+            }
+        };
+        let first: Vec<_> = (0..15).map(|_| x.next()).collect();
+        assert_eq!(first, [1, 2, 0, 4, -1, 0, 7, 8, 0, -1, 11, 0, 13, 14, -2]);
+    }
+
+    #[test]
+    fn infinite_gen_match() {
+        let mut x = infinite_gen! {
+            state n: u8 = 0;
+            loop {
+                match n % 3 {
+                    0 => n += 10,
+                    _ => n += 1,
+                }
+                yield n;
+            }
+        };
+        assert_eq!((x.next(), x.next(), x.next(), x.next()), (10, 11, 12, 22));
+    }
+
+    #[test]
+    fn infinite_gen_while_for() {
+        // 1, 11, 111, ... in binary, each followed by its number of ones
+        let mut x = infinite_gen! {
+            state len: u32 = 0, n: u32 = 0;
+            loop {
+                len += 1;
+                n = 0;
+                for i in 0..len {
+                    n += 1 << i;
+                }
+                yield n;
+                let mut ones = 0;
+                while n > 0 {
+                    ones += n & 1;
+                    n >>= 1;
+                }
+                yield ones;
+            }
+        };
+        let first: Vec<_> = (0..6).map(|_| x.next()).collect();
+        assert_eq!(first, [1, 1, 3, 2, 7, 3]);
+    }
+
+    #[test]
+    fn infinite_gen_let_else() {
+        let mut x = infinite_gen! {
+            state input: Vec<&'static str> = vec!["1", "x", "3"];
+            loop {
+                let text = input.remove(0);
+                let Ok(n) = text.parse::<u32>() else {
+                    panic!("not a number: {}", text)
+                };
+                yield n;
+            }
+        };
+        assert_eq!(x.next(), 1);
+        assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| x.next())).is_err());
+    }
+
+    #[test]
+    fn infinite_gen_brace_macros() {
+        let mut x = infinite_gen! {
+            state v: Vec<u8> = vec![];
+            loop -> Vec<u8> {
+                v.push(1);
+                yield v.clone();
+                assert! { v.len() < 3 }
+                v = vec![v.len() as u8];
+                yield v.clone();
+            }
+        };
+        assert_eq!(
+            (x.next(), x.next(), x.next()),
+            (vec![1], vec![1], vec![1, 1])
+        );
+        assert_eq!(x.next(), vec![2]);
+    }
 }
@@ -0,0 +1,173 @@
+//! Random access into infinite iterators by caching their items
+
+use super::InfiniteIterator;
+use alloc::collections::VecDeque;
+
+/// Caches the items of an infinite iterator so they can be accessed by index
+///
+/// Items are produced lazily, the first time they (or a later item) are accessed.
+/// To bound memory, a prefix of the cache can be evicted,
+/// either manually with [`evict_before`](#method.evict_before)
+/// or automatically with [`with_limit`](#method.with_limit).
+#[derive(Debug, Clone)]
+pub struct Memo<I: InfiniteIterator> {
+    iter: I,
+    cache: VecDeque<I::Item>,
+    /// The index of `cache[0]`
+    offset: usize,
+    limit: Option<usize>,
+}
+
+impl<I: InfiniteIterator> Memo<I> {
+    pub fn new(iter: I) -> Self {
+        Self {
+            iter,
+            cache: VecDeque::new(),
+            offset: 0,
+            limit: None,
+        }
+    }
+
+    /// Keeps at most `limit` items cached, evicting the oldest ones when more are produced
+    ///
+    /// # Panics
+    /// If `limit` is `0`
+    pub fn with_limit(iter: I, limit: usize) -> Self {
+        assert!(limit > 0, "Memo::with_limit: limit must be positive");
+        Self {
+            limit: Some(limit),
+            ..Self::new(iter)
+        }
+    }
+
+    /// The index of the first item that is still cached (or will be, once it's produced)
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The number of items produced so far
+    pub fn produced(&self) -> usize {
+        self.offset + self.cache.len()
+    }
+
+    /// Returns a reference to the item at index `k`, producing it and all before it if needed
+    ///
+    /// # Panics
+    /// If the item was already evicted, i.e. `k < self.offset()`
+    pub fn get(&mut self, k: usize) -> &I::Item {
+        self.get_mut(k)
+    }
+
+    /// Returns a mutable reference to the item at index `k`, producing it and all before it if needed
+    ///
+    /// # Panics
+    /// If the item was already evicted, i.e. `k < self.offset()`
+    pub fn get_mut(&mut self, k: usize) -> &mut I::Item {
+        assert!(k >= self.offset, "Memo: item {} was already evicted", k);
+        while self.produced() <= k {
+            if self.limit == Some(self.cache.len()) {
+                self.cache.pop_front();
+                self.offset += 1;
+            }
+            self.cache.push_back(self.iter.next());
+        }
+        &mut self.cache[k - self.offset]
+    }
+
+    /// Drops all cached items before index `k`, they can't be accessed anymore afterwards
+    pub fn evict_before(&mut self, k: usize) {
+        let produced = self.produced();
+        let n = k.saturating_sub(self.offset).min(self.cache.len());
+        self.cache.drain(..n);
+        // items that weren't produced yet are skipped right away
+        for _ in produced..k {
+            self.iter.next();
+        }
+        self.offset = self.offset.max(k);
+    }
+
+    /// Iterates over clones of the items, starting at index `k`
+    ///
+    /// # Panics
+    /// Whenever an item is reached that was already evicted
+    pub fn iter_from(&mut self, k: usize) -> MemoIter<'_, I>
+    where
+        I::Item: Clone,
+    {
+        MemoIter { memo: self, pos: k }
+    }
+}
+
+pub struct MemoIter<'a, I: InfiniteIterator> {
+    memo: &'a mut Memo<I>,
+    pos: usize,
+}
+
+impl<'a, I: InfiniteIterator> InfiniteIterator for MemoIter<'a, I>
+where
+    I::Item: Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> I::Item {
+        let x = self.memo.get(self.pos).clone();
+        self.pos += 1;
+        x
+    }
+
+    fn nth(&mut self, n: usize) -> I::Item {
+        self.pos += n;
+        self.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sequences::fibonacci;
+    use std::cell::Cell;
+
+    #[test]
+    fn get() {
+        let produced = Cell::new(0);
+        let mut memo = Memo::new(crate::from_fn(|| {
+            produced.set(produced.get() + 1);
+            produced.get() * 10
+        }));
+        assert_eq!(*memo.get(3), 40);
+        assert_eq!(produced.get(), 4);
+        assert_eq!(*memo.get(0), 10);
+        assert_eq!(*memo.get(3), 40);
+        assert_eq!(produced.get(), 4);
+        *memo.get_mut(1) = 0;
+        assert_eq!(*memo.get(1), 0);
+    }
+
+    #[test]
+    fn iter_from() {
+        let mut memo = Memo::new(fibonacci::<u32>());
+        let mut x = memo.iter_from(5);
+        assert_eq!((x.next(), x.next(), x.nth(1)), (5, 8, 21));
+        let mut y = memo.iter_from(0);
+        assert_eq!((y.next(), y.next(), y.next()), (0, 1, 1));
+        assert_eq!(memo.produced(), 9);
+    }
+
+    #[test]
+    fn eviction() {
+        let mut memo = Memo::new(fibonacci::<u32>());
+        memo.get(4);
+        memo.evict_before(2);
+        assert_eq!(memo.offset(), 2);
+        assert_eq!(*memo.get(2), 1);
+        memo.evict_before(7);
+        assert_eq!(memo.produced(), 7);
+        assert_eq!(*memo.get(7), 13);
+        assert!(std::panic::catch_unwind(move || *memo.get(6)).is_err());
+
+        let mut bounded = Memo::with_limit(fibonacci::<u32>(), 3);
+        assert_eq!(*bounded.get(10), 55);
+        assert_eq!(bounded.offset(), 8);
+        assert_eq!(*bounded.get(8), 21);
+    }
+}
@@ -0,0 +1,321 @@
+//! Small seedable pseudo random number generators and adaptors on top of them
+//!
+//! These are meant for reproducible tests and simulations, not for cryptography.
+
+use super::InfiniteIterator;
+use core::ops::Range;
+
+/// Marsaglia's xorshift generator with 64 bits of state
+#[derive(Debug, Clone)]
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    /// Note that a seed of `0` is replaced by another constant,
+    /// since the generator would only ever return `0` otherwise
+    pub fn new(seed: u64) -> Self {
+        let state = if seed == 0 {
+            0x9e37_79b9_7f4a_7c15
+        } else {
+            seed
+        };
+        Self { state }
+    }
+}
+
+impl InfiniteIterator for XorShift64 {
+    type Item = u64;
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+}
+
+/// The SplitMix64 generator, which accepts any seed
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+}
+
+impl InfiniteIterator for SplitMix64 {
+    type Item = u64;
+
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// The PCG32 (XSH RR) generator
+///
+/// Every `u64` is made of two consecutive 32 bit outputs, the first one in the high half.
+#[derive(Debug, Clone)]
+pub struct Pcg32 {
+    state: u64,
+    inc: u64,
+}
+
+impl Pcg32 {
+    const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+    const DEFAULT_STREAM: u64 = 0x2c9f_fd0e_d7a4_9f8d;
+
+    pub fn new(seed: u64) -> Self {
+        Self::with_stream(seed, Self::DEFAULT_STREAM)
+    }
+
+    /// Generators with different `stream`s produce different sequences from the same seed
+    pub fn with_stream(seed: u64, stream: u64) -> Self {
+        let mut rng = Self {
+            state: 0,
+            inc: (stream << 1) | 1,
+        };
+        rng.next_u32();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.next_u32();
+        rng
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = old.wrapping_mul(Self::MULTIPLIER).wrapping_add(self.inc);
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        xorshifted.rotate_right((old >> 59) as u32)
+    }
+}
+
+impl InfiniteIterator for Pcg32 {
+    type Item = u64;
+
+    fn next(&mut self) -> u64 {
+        let high = self.next_u32() as u64;
+        (high << 32) | self.next_u32() as u64
+    }
+}
+
+/// Adaptors for infinite iterators of random `u64`s
+pub trait Random: InfiniteIterator<Item = u64> + Sized {
+    /// Uniformly distributed numbers in `range`
+    ///
+    /// # Panics
+    /// If `range` is empty
+    fn uniform(self, range: Range<u64>) -> Uniform<Self> {
+        assert!(range.start < range.end, "uniform: empty range");
+        Uniform {
+            rng: self,
+            start: range.start,
+            len: range.end - range.start,
+        }
+    }
+
+    /// Uniformly distributed floats in `[0, 1)`
+    fn floats(self) -> Floats<Self> {
+        Floats { rng: self }
+    }
+
+    /// Booleans that are `true` with probability `p`
+    ///
+    /// # Panics
+    /// If `p` is not in `[0, 1]`
+    fn bernoulli(self, p: f64) -> Bernoulli<Self> {
+        assert!((0.0..=1.0).contains(&p), "bernoulli: p is not in [0, 1]");
+        Bernoulli {
+            floats: self.floats(),
+            p,
+        }
+    }
+
+    /// Uniformly chosen elements of `slice`, with replacement
+    ///
+    /// # Panics
+    /// If `slice` is empty
+    fn choose<T>(self, slice: &[T]) -> Choose<'_, Self, T> {
+        assert!(!slice.is_empty(), "choose: empty slice");
+        Choose {
+            indices: self.uniform(0..slice.len() as u64),
+            slice,
+        }
+    }
+
+    /// All elements of `slice` in random order, then again in another random order and so on
+    ///
+    /// The slice is shuffled in place as the items are produced.
+    ///
+    /// # Panics
+    /// If `slice` is empty
+    fn shuffled<T: Clone>(self, slice: &mut [T]) -> Shuffled<'_, Self, T> {
+        assert!(!slice.is_empty(), "shuffled: empty slice");
+        Shuffled {
+            rng: self,
+            slice,
+            pos: 0,
+        }
+    }
+}
+
+impl<I: InfiniteIterator<Item = u64>> Random for I {}
+
+#[derive(Debug, Clone)]
+pub struct Uniform<R> {
+    rng: R,
+    start: u64,
+    len: u64,
+}
+
+impl<R: InfiniteIterator<Item = u64>> InfiniteIterator for Uniform<R> {
+    type Item = u64;
+
+    fn next(&mut self) -> u64 {
+        // Lemire's multiply-and-reject method, which avoids modulo bias
+        let threshold = self.len.wrapping_neg() % self.len;
+        loop {
+            let m = self.rng.next() as u128 * self.len as u128;
+            if m as u64 >= threshold {
+                return self.start + (m >> 64) as u64;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Floats<R> {
+    rng: R,
+}
+
+impl<R: InfiniteIterator<Item = u64>> InfiniteIterator for Floats<R> {
+    type Item = f64;
+
+    fn next(&mut self) -> f64 {
+        // the 53 high bits fill the mantissa exactly
+        (self.rng.next() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Bernoulli<R> {
+    floats: Floats<R>,
+    p: f64,
+}
+
+impl<R: InfiniteIterator<Item = u64>> InfiniteIterator for Bernoulli<R> {
+    type Item = bool;
+
+    fn next(&mut self) -> bool {
+        self.floats.next() < self.p
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Choose<'a, R, T> {
+    indices: Uniform<R>,
+    slice: &'a [T],
+}
+
+impl<'a, R: InfiniteIterator<Item = u64>, T> InfiniteIterator for Choose<'a, R, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> &'a T {
+        &self.slice[self.indices.next() as usize]
+    }
+}
+
+#[derive(Debug)]
+pub struct Shuffled<'a, R, T> {
+    rng: R,
+    slice: &'a mut [T],
+    /// Everything before `pos` was already returned in this round
+    pos: usize,
+}
+
+impl<'a, R: InfiniteIterator<Item = u64>, T: Clone> InfiniteIterator for Shuffled<'a, R, T> {
+    type Item = T;
+
+    fn next(&mut self) -> T {
+        if self.pos == self.slice.len() {
+            self.pos = 0;
+        }
+        // one step of a Fisher-Yates shuffle
+        let remaining = (self.slice.len() - self.pos) as u64;
+        let j = self.pos + (&mut self.rng).uniform(0..remaining).next() as usize;
+        self.slice.swap(self.pos, j);
+        self.pos += 1;
+        self.slice[self.pos - 1].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn take<I: InfiniteIterator>(mut i: I, n: usize) -> Vec<I::Item> {
+        (0..n).map(|_| i.next()).collect()
+    }
+
+    #[test]
+    fn reference_values() {
+        assert_eq!(
+            take(XorShift64::new(1), 2),
+            [1_082_269_761, 1_152_992_998_833_853_505]
+        );
+        assert_eq!(
+            take(SplitMix64::new(0), 2),
+            [0xe220_a839_7b1d_cdaf, 0x6e78_9e6a_a1b9_65f4]
+        );
+        let mut pcg = Pcg32::with_stream(42, 54);
+        assert_eq!(pcg.next_u32(), 0xa15c_02b7);
+        assert_eq!(pcg.next_u32(), 0x7b47_f409);
+        assert_eq!(pcg.next(), 0xba1d_3330_83d2_f293);
+    }
+
+    #[test]
+    fn seeds() {
+        assert_eq!(take(Pcg32::new(7), 10), take(Pcg32::new(7), 10));
+        assert_ne!(take(Pcg32::new(7), 10), take(Pcg32::new(8), 10));
+        assert_ne!(take(XorShift64::new(0), 1), [0]);
+    }
+
+    #[test]
+    fn uniform_floats_bernoulli() {
+        let xs = take(SplitMix64::new(1).uniform(10..13), 1000);
+        assert!(xs.iter().all(|x| (10..13).contains(x)));
+        assert!((10..13).all(|n| xs.contains(&n)));
+
+        let fs = take(SplitMix64::new(2).floats(), 1000);
+        assert!(fs.iter().all(|f| (0.0..1.0).contains(f)));
+
+        let bs = take(SplitMix64::new(3).bernoulli(0.25), 10_000);
+        let trues = bs.iter().filter(|b| **b).count();
+        assert!((2000..3000).contains(&trues));
+        assert!(!take(SplitMix64::new(3).bernoulli(0.0), 100).contains(&true));
+        assert!(!take(SplitMix64::new(3).bernoulli(1.0), 100).contains(&false));
+    }
+
+    #[test]
+    fn choose_shuffled() {
+        let v = ['a', 'b', 'c'];
+        let xs = take(XorShift64::new(5).choose(&v), 100);
+        assert!(v.iter().all(|c| xs.contains(&c)));
+
+        let mut w = [1, 2, 3, 4, 5];
+        let mut ys = take(XorShift64::new(5).shuffled(&mut w), 10);
+        let (first, second) = ys.split_at_mut(5);
+        first.sort_unstable();
+        second.sort_unstable();
+        assert_eq!(first, [1, 2, 3, 4, 5]);
+        assert_eq!(second, [1, 2, 3, 4, 5]);
+    }
+}
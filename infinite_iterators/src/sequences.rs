@@ -0,0 +1,485 @@
+//! Well-known integer sequences
+
+use super::InfiniteIterator;
+#[cfg(feature = "alloc")]
+use alloc::collections::BinaryHeap;
+#[cfg(feature = "alloc")]
+use core::cmp::Reverse;
+use core::ops::RangeFrom;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// The primitive integer types
+pub trait Integer: sealed::Sealed + Copy + Ord {
+    const ZERO: Self;
+    const ONE: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    fn from_usize(n: usize) -> Option<Self>;
+    /// Converts modulo the size of `Self`
+    fn from_usize_wrapping(n: usize) -> Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn saturating_add(self, rhs: Self) -> Self;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn wrapping_mul(self, rhs: Self) -> Self;
+    fn saturating_mul(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty)*) => {$(
+        impl sealed::Sealed for $t {}
+
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+
+            fn from_usize(n: usize) -> Option<Self> {
+                core::convert::TryFrom::try_from(n).ok()
+            }
+            fn from_usize_wrapping(n: usize) -> Self {
+                n as $t
+            }
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+            fn wrapping_add(self, rhs: Self) -> Self {
+                <$t>::wrapping_add(self, rhs)
+            }
+            fn saturating_add(self, rhs: Self) -> Self {
+                <$t>::saturating_add(self, rhs)
+            }
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+            fn wrapping_mul(self, rhs: Self) -> Self {
+                <$t>::wrapping_mul(self, rhs)
+            }
+            fn saturating_mul(self, rhs: Self) -> Self {
+                <$t>::saturating_mul(self, rhs)
+            }
+        }
+    )*};
+}
+
+impl_integer!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+/// What to do when the next item of a sequence doesn't fit into its type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Overflow {
+    /// Wrap around at the boundary of the type
+    Wrapping,
+    /// Stay at the boundary of the type forever
+    Saturating,
+    /// Panic when the item that doesn't fit would be returned
+    Panic,
+}
+
+impl Overflow {
+    fn add<T: Integer>(self, a: T, b: T) -> Option<T> {
+        match self {
+            Overflow::Wrapping => Some(a.wrapping_add(b)),
+            Overflow::Saturating => Some(a.saturating_add(b)),
+            Overflow::Panic => a.checked_add(b),
+        }
+    }
+
+    fn mul<T: Integer>(self, a: T, b: T) -> Option<T> {
+        match self {
+            Overflow::Wrapping => Some(a.wrapping_mul(b)),
+            Overflow::Saturating => Some(a.saturating_mul(b)),
+            Overflow::Panic => a.checked_mul(b),
+        }
+    }
+}
+
+impl Overflow {
+    /// `start + n * step`, as if `add` was applied `n` times
+    fn advance<T: Integer>(self, start: T, step: T, n: usize) -> Option<T> {
+        if step == T::ZERO {
+            return Some(start);
+        }
+        if self == Overflow::Wrapping {
+            return Some(start.wrapping_add(step.wrapping_mul(T::from_usize_wrapping(n))));
+        }
+        if let Some(offset) = T::from_usize(n).and_then(|n| step.checked_mul(n)) {
+            return self.add(start, offset);
+        }
+        // `n * step` is out of range, so unless `start` and `step` have opposite signs
+        // (or `start` is zero, where `n * step` might be exactly `MIN`), the result is too
+        let towards_zero =
+            (start <= T::ZERO && step > T::ZERO) || (start >= T::ZERO && step < T::ZERO);
+        if !towards_zero {
+            return match self {
+                Overflow::Saturating if step > T::ZERO => Some(T::MAX),
+                Overflow::Saturating => Some(T::MIN),
+                _ => None,
+            };
+        }
+        // since the sequence is monotonic, this is the same as advancing by `n` at once
+        let half = n / 2;
+        self.advance(self.advance(start, step, half)?, step, n - half)
+    }
+}
+
+/// `None` marks an overflow under `Overflow::Panic`, which is only reported
+/// once the item is actually requested
+fn unwrap_item<T>(item: Option<T>) -> T {
+    item.expect("integer overflow in infinite sequence")
+}
+
+/// `n, n + 1, n + 2, ...`
+///
+/// This is the infinite counterpart of `n..`, which is only an `Iterator`
+/// (but can be converted with `CountFrom::from(n..)`).
+/// Unlike `n..`, it can skip ahead in constant time.
+pub fn count_from<T: Integer>(n: T, overflow: Overflow) -> CountFrom<T> {
+    CountFrom {
+        next: Some(n),
+        overflow,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CountFrom<T> {
+    next: Option<T>,
+    overflow: Overflow,
+}
+
+impl<T: Integer> InfiniteIterator for CountFrom<T> {
+    type Item = T;
+
+    fn next(&mut self) -> T {
+        let n = unwrap_item(self.next);
+        self.next = self.overflow.add(n, T::ONE);
+        n
+    }
+
+    fn advance_by(&mut self, n: usize) {
+        if n > 0 {
+            let start = unwrap_item(self.next);
+            self.next = self.overflow.advance(start, T::ONE, n);
+        }
+    }
+}
+
+/// Panics on overflow, like `n..` does with debug assertions
+impl<T: Integer> From<RangeFrom<T>> for CountFrom<T> {
+    fn from(range: RangeFrom<T>) -> Self {
+        count_from(range.start, Overflow::Panic)
+    }
+}
+
+/// `start, start + step, start + 2 * step, ...`
+///
+/// This panics on overflow, use [`Arithmetic::overflow`] to change that.
+pub fn arithmetic<T: Integer>(start: T, step: T) -> Arithmetic<T> {
+    Arithmetic {
+        next: Some(start),
+        step,
+        overflow: Overflow::Panic,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Arithmetic<T> {
+    next: Option<T>,
+    step: T,
+    overflow: Overflow,
+}
+
+impl<T> Arithmetic<T> {
+    pub fn overflow(self, overflow: Overflow) -> Self {
+        Self { overflow, ..self }
+    }
+}
+
+impl<T: Integer> InfiniteIterator for Arithmetic<T> {
+    type Item = T;
+
+    fn next(&mut self) -> T {
+        let n = unwrap_item(self.next);
+        self.next = self.overflow.add(n, self.step);
+        n
+    }
+
+    fn advance_by(&mut self, n: usize) {
+        if n > 0 {
+            let start = unwrap_item(self.next);
+            self.next = self.overflow.advance(start, self.step, n);
+        }
+    }
+}
+
+/// `start, start * ratio, start * ratio^2, ...`
+///
+/// This panics on overflow, use [`Geometric::overflow`] to change that.
+pub fn geometric<T: Integer>(start: T, ratio: T) -> Geometric<T> {
+    Geometric {
+        next: Some(start),
+        ratio,
+        overflow: Overflow::Panic,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Geometric<T> {
+    next: Option<T>,
+    ratio: T,
+    overflow: Overflow,
+}
+
+impl<T> Geometric<T> {
+    pub fn overflow(self, overflow: Overflow) -> Self {
+        Self { overflow, ..self }
+    }
+}
+
+impl<T: Integer> InfiniteIterator for Geometric<T> {
+    type Item = T;
+
+    fn next(&mut self) -> T {
+        let n = unwrap_item(self.next);
+        self.next = self.overflow.mul(n, self.ratio);
+        n
+    }
+}
+
+/// `0, 1, 1, 2, 3, 5, 8, ...`
+///
+/// This panics once an item doesn't fit into `T`.
+pub fn fibonacci<T: Integer>() -> Fibonacci<T> {
+    Fibonacci {
+        current: Some(T::ZERO),
+        next: Some(T::ONE),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Fibonacci<T> {
+    current: Option<T>,
+    next: Option<T>,
+}
+
+impl<T: Integer> InfiniteIterator for Fibonacci<T> {
+    type Item = T;
+
+    fn next(&mut self) -> T {
+        let n = unwrap_item(self.current);
+        self.current = self.next;
+        self.next = self.next.and_then(|m| m.checked_add(n));
+        n
+    }
+}
+
+/// `0, 1, 3, 6, 10, ...`, the sums of the first `n` positive integers
+///
+/// This panics once an item doesn't fit into `T`.
+pub fn triangular<T: Integer>() -> Triangular<T> {
+    Triangular {
+        next: Some(T::ZERO),
+        n: T::ZERO,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Triangular<T> {
+    next: Option<T>,
+    /// The index of `next`
+    n: T,
+}
+
+impl<T: Integer> InfiniteIterator for Triangular<T> {
+    type Item = T;
+
+    fn next(&mut self) -> T {
+        let t = unwrap_item(self.next);
+        self.next = self.n.checked_add(T::ONE).and_then(|n| {
+            self.n = n;
+            t.checked_add(n)
+        });
+        t
+    }
+}
+
+/// `2, 3, 5, 7, 11, ...`, using an incremental sieve of Eratosthenes
+///
+/// Memory grows with the number of primes produced so far.
+#[cfg(feature = "alloc")]
+pub fn primes() -> Primes {
+    Primes {
+        candidate: 2,
+        composites: BinaryHeap::new(),
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct Primes {
+    /// The next number to test
+    candidate: u64,
+    /// The next odd multiple of every odd prime so far, along with the distance to the one after
+    composites: BinaryHeap<Reverse<(u64, u64)>>,
+}
+
+#[cfg(feature = "alloc")]
+impl InfiniteIterator for Primes {
+    type Item = u64;
+
+    fn next(&mut self) -> u64 {
+        if self.candidate == 2 {
+            self.candidate = 3;
+            return 2;
+        }
+        loop {
+            let c = self.candidate;
+            self.candidate += 2;
+            let mut is_prime = true;
+            while let Some(&Reverse((multiple, step))) = self.composites.peek() {
+                if multiple != c {
+                    break;
+                }
+                is_prime = false;
+                self.composites.pop();
+                self.composites.push(Reverse((multiple + step, step)));
+            }
+            if is_prime {
+                // smaller multiples of `c` have smaller prime factors and are covered already
+                // if the square overflows, no multiple of `c` will ever be tested
+                if let Some(square) = c.checked_mul(c) {
+                    self.composites.push(Reverse((square, 2 * c)));
+                }
+                return c;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn take<I: InfiniteIterator>(mut i: I, n: usize) -> Vec<I::Item> {
+        (0..n).map(|_| i.next()).collect()
+    }
+
+    #[test]
+    fn count_from_overflow() {
+        assert_eq!(take(count_from(3u32, Overflow::Panic), 3), [3, 4, 5]);
+        assert_eq!(
+            take(count_from(254u8, Overflow::Wrapping), 4),
+            [254, 255, 0, 1]
+        );
+        assert_eq!(
+            take(count_from(254u8, Overflow::Saturating), 4),
+            [254, 255, 255, 255]
+        );
+        let mut x = count_from(254u8, Overflow::Panic);
+        assert_eq!(take(&mut x, 2), [254, 255]);
+        assert!(std::panic::catch_unwind(move || x.next()).is_err());
+    }
+
+    #[test]
+    fn count_from_range() {
+        let mut x = CountFrom::from(5u64..);
+        assert_eq!(take(&mut x, 2), [5, 6]);
+        assert_eq!(x.nth(1_000_000), 1_000_007);
+        let mut y: CountFrom<i8> = (126..).into();
+        assert_eq!(take(&mut y, 2), [126, 127]);
+        assert!(std::panic::catch_unwind(move || y.next()).is_err());
+    }
+
+    #[test]
+    fn arithmetic_geometric() {
+        assert_eq!(take(arithmetic(10i32, -3), 4), [10, 7, 4, 1]);
+        assert_eq!(
+            take(arithmetic(250u8, 3).overflow(Overflow::Wrapping), 3),
+            [250, 253, 0]
+        );
+        assert_eq!(take(geometric(1u64, 3), 5), [1, 3, 9, 27, 81]);
+        assert_eq!(
+            take(geometric(64u8, 2).overflow(Overflow::Saturating), 3),
+            [64, 128, 255]
+        );
+        let mut x = geometric(64u8, 2);
+        assert_eq!(take(&mut x, 2), [64, 128]);
+        assert!(std::panic::catch_unwind(move || x.next()).is_err());
+    }
+
+    #[test]
+    fn fibonacci_triangular() {
+        assert_eq!(
+            take(fibonacci::<u32>(), 10),
+            [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]
+        );
+        // 233 is the last fibonacci number that fits into a u8
+        let mut x = fibonacci::<u8>();
+        assert_eq!(take(&mut x, 14)[13], 233);
+        assert!(std::panic::catch_unwind(move || x.next()).is_err());
+
+        assert_eq!(take(triangular::<u16>(), 6), [0, 1, 3, 6, 10, 15]);
+        // 253 is the last triangular number that fits into a u8
+        let mut y = triangular::<u8>();
+        assert_eq!(take(&mut y, 23)[22], 253);
+        assert!(std::panic::catch_unwind(move || y.next()).is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn primes_sieve() {
+        assert_eq!(
+            take(primes(), 12),
+            [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37]
+        );
+        let naive = (2u64..)
+            .filter(|n| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0))
+            .take(1000);
+        assert!(take(primes(), 1000).into_iter().eq(naive));
+    }
+
+    #[test]
+    fn advance_by_matches_stepping() {
+        fn check<T: Integer + std::fmt::Debug>(start: T, step: T, overflow: Overflow, n: usize) {
+            let mut fast = arithmetic(start, step).overflow(overflow);
+            let mut slow = fast.clone();
+            fast.advance_by(n);
+            for _ in 0..n {
+                if slow.next.is_none() {
+                    break;
+                }
+                slow.next();
+            }
+            assert_eq!(
+                fast.next, slow.next,
+                "{:?} {:?} {:?} {}",
+                start, step, overflow, n
+            );
+        }
+        for &overflow in &[Overflow::Wrapping, Overflow::Saturating, Overflow::Panic] {
+            for start in (-128i8..=127).step_by(17) {
+                for &step in &[-128i8, -100, -3, -1, 0, 1, 2, 50, 127] {
+                    for n in 0..300 {
+                        check(start, step, overflow, n);
+                    }
+                }
+            }
+            for start in (0u8..=255).step_by(15) {
+                for &step in &[0u8, 1, 7, 200] {
+                    for n in 0..300 {
+                        check(start, step, overflow, n);
+                    }
+                }
+            }
+        }
+        let mut x = count_from(5u64, Overflow::Panic);
+        x.advance_by(1_000_000_000_000);
+        assert_eq!(x.next(), 1_000_000_000_005);
+    }
+}
@@ -0,0 +1,377 @@
+//! The [`AtLeast`](struct.AtLeast.html) type, a vector with a const-generic minimum length
+
+use alloc::vec::{self, Vec};
+use core::borrow::Borrow;
+use core::convert::TryFrom;
+use core::ops::{Index, IndexMut};
+use core::slice;
+
+/// Compile-time bounds checks for const indices
+struct Assert<const I: usize, const N: usize>;
+
+impl<const I: usize, const N: usize> Assert<I, N> {
+    const LESS: () = assert!(I < N, "const index out of the guaranteed range");
+}
+
+/// A `Vec` that always has at least `N` elements
+///
+/// The elements are stored contiguously in a single `Vec`,
+/// so `Eq`, `Ord` and `Hash` agree with those of `[T]`.
+///
+/// [`NonemptyVec`](../struct.NonemptyVec.html) is built on `AtLeast<T, 1>`,
+/// and both can be converted into each other for free with `From`.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct AtLeast<T, const N: usize> {
+    // invariant: this has at least `N` elements
+    vec: Vec<T>,
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl<T, const N: usize> AtLeast<T, N> {
+    /// Wraps a `Vec`, which must have at least `N` elements
+    pub(crate) fn from_vec_unchecked(vec: Vec<T>) -> Self {
+        debug_assert!(vec.len() >= N);
+        Self { vec }
+    }
+
+    /// Gives access to the underlying `Vec`;
+    /// the caller must leave at least `N` elements in it
+    pub(crate) fn as_mut_vec(&mut self) -> &mut Vec<T> {
+        &mut self.vec
+    }
+
+    /// Creates a new `AtLeast` with exactly the guaranteed elements
+    pub fn new(prefix: [T; N]) -> Self {
+        Self::from_vec_unchecked(Vec::from(prefix))
+    }
+
+    /// Creates a new `AtLeast` with exactly the guaranteed elements
+    /// and space for at least `capacity` elements in total
+    pub fn with_capacity(prefix: [T; N], capacity: usize) -> Self {
+        let mut vec = Vec::with_capacity(capacity.max(N));
+        vec.extend(IntoIterator::into_iter(prefix));
+        Self::from_vec_unchecked(vec)
+    }
+
+    /// Creates an `AtLeast` from its guaranteed elements and the rest;
+    /// this is the inverse of [`into_prefix_rest`](#method.into_prefix_rest)
+    pub fn from_prefix_rest(prefix: [T; N], rest: Vec<T>) -> Self {
+        let mut res = Self::with_capacity(prefix, N + rest.len());
+        res.vec.extend(rest);
+        res
+    }
+
+    /// The first `N` elements, which are guaranteed to exist
+    pub fn prefix(&self) -> &[T; N] {
+        <&[T; N]>::try_from(&self.vec[..N]).unwrap()
+    }
+
+    /// Like `prefix` but mutable
+    pub fn prefix_mut(&mut self) -> &mut [T; N] {
+        <&mut [T; N]>::try_from(&mut self.vec[..N]).unwrap()
+    }
+
+    /// All elements after the first `N`
+    pub fn rest(&self) -> &[T] {
+        &self.vec[N..]
+    }
+
+    /// Like `rest` but mutable
+    pub fn rest_mut(&mut self) -> &mut [T] {
+        &mut self.vec[N..]
+    }
+
+    /// Consumes `self` and returns the guaranteed elements and the rest
+    ///
+    /// This moves the rest into a new `Vec`.
+    pub fn into_prefix_rest(mut self) -> ([T; N], Vec<T>) {
+        let rest = self.vec.split_off(N);
+        match <[T; N]>::try_from(self.vec) {
+            Ok(prefix) => (prefix, rest),
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Returns all elements as a slice
+    pub fn as_slice(&self) -> &[T] {
+        &self.vec
+    }
+
+    /// Like `as_slice` but mutable
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.vec
+    }
+
+    /// Returns the length of `self`, which is always at least `N`
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Returns the number of elements `self` can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    /// Reserves space for at least `additional` more elements
+    pub fn reserve(&mut self, additional: usize) {
+        self.vec.reserve(additional)
+    }
+
+    /// Shrinks the capacity as much as possible
+    pub fn shrink_to_fit(&mut self) {
+        self.vec.shrink_to_fit()
+    }
+
+    /// Returns the element at the guaranteed index `I`
+    ///
+    /// Using an `I` that is not less than `N` is a compile-time error:
+    /// ```compile_fail
+    /// # use nonempty_vec::AtLeast;
+    /// let v = AtLeast::new([1, 2]);
+    /// v.get_const::<2>();
+    /// ```
+    pub fn get_const<const I: usize>(&self) -> &T {
+        let () = Assert::<I, N>::LESS;
+        &self.vec[I]
+    }
+
+    /// Like `get_const` but mutable
+    pub fn get_const_mut<const I: usize>(&mut self) -> &mut T {
+        let () = Assert::<I, N>::LESS;
+        &mut self.vec[I]
+    }
+
+    /// The first element
+    ///
+    /// Calling this on an `AtLeast<T, 0>` is a compile-time error.
+    pub fn first(&self) -> &T {
+        self.get_const::<0>()
+    }
+
+    /// Like `first` but mutable
+    pub fn first_mut(&mut self) -> &mut T {
+        self.get_const_mut::<0>()
+    }
+
+    /// The last element
+    ///
+    /// Calling this on an `AtLeast<T, 0>` is a compile-time error.
+    pub fn last(&self) -> &T {
+        let () = Assert::<0, N>::LESS;
+        self.vec.last().unwrap()
+    }
+
+    /// Like `last` but mutable
+    pub fn last_mut(&mut self) -> &mut T {
+        let () = Assert::<0, N>::LESS;
+        self.vec.last_mut().unwrap()
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if it is out of bounds
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.vec.get(index)
+    }
+
+    /// Like `get` but mutable
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.vec.get_mut(index)
+    }
+
+    /// Pushes an element to the end of `self`
+    pub fn push(&mut self, t: T) {
+        self.vec.push(t)
+    }
+
+    /// Removes the last element from `self`
+    /// (unless `self` has only `N` items left), returns it.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.vec.len() > N {
+            self.vec.pop()
+        } else {
+            None
+        }
+    }
+
+    /// Inserts an element at the specified index
+    pub fn insert(&mut self, index: usize, element: T) {
+        assert!(
+            index <= self.len(),
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            self.len()
+        );
+        self.vec.insert(index, element)
+    }
+
+    /// Removes an element from the specified index;
+    /// Returns `None` if there are only `N` elements left
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        assert!(
+            index < self.len(),
+            "removal index (is {}) should be < len (is {})",
+            index,
+            self.len()
+        );
+        if self.vec.len() > N {
+            Some(self.vec.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Shortens `self` to `len` elements, dropping the rest
+    ///
+    /// The guaranteed elements are never dropped, so a `len` below `N` acts like `N`.
+    pub fn truncate(&mut self, len: usize) {
+        self.vec.truncate(len.max(N))
+    }
+
+    /// Splits off the elements starting at index `at`, returning them
+    ///
+    /// # Panics
+    /// Panics if `at < N` or `at > len`
+    pub fn split_off(&mut self, at: usize) -> Vec<T> {
+        assert!(
+            N <= at && at <= self.len(),
+            "split index (is {}) should be in {}..={}",
+            at,
+            N,
+            self.len()
+        );
+        self.vec.split_off(at)
+    }
+
+    /// Returns an iterator over references to the elements
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.vec.iter()
+    }
+
+    /// Returns an iterator over mutable references to the elements
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.vec.iter_mut()
+    }
+
+    /// Creates a `Vec` from all its elements
+    pub fn into_vec(self) -> Vec<T> {
+        self.vec
+    }
+}
+
+impl<T, const N: usize> Index<usize> for AtLeast<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.vec[index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for AtLeast<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.vec[index]
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for AtLeast<T, N> {
+    fn as_ref(&self) -> &[T] {
+        &self.vec
+    }
+}
+
+impl<T, const N: usize> Borrow<[T]> for AtLeast<T, N> {
+    fn borrow(&self) -> &[T] {
+        &self.vec
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for AtLeast<T, N> {
+    fn from(prefix: [T; N]) -> Self {
+        Self::new(prefix)
+    }
+}
+
+impl<T, const N: usize> TryFrom<Vec<T>> for AtLeast<T, N> {
+    /// The original `Vec` is returned if it is too short
+    type Error = Vec<T>;
+
+    fn try_from(vec: Vec<T>) -> Result<Self, Vec<T>> {
+        if vec.len() < N {
+            Err(vec)
+        } else {
+            Ok(Self::from_vec_unchecked(vec))
+        }
+    }
+}
+
+impl<T, const N: usize> Extend<T> for AtLeast<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.vec.extend(iter)
+    }
+}
+
+impl<T, const N: usize> IntoIterator for AtLeast<T, N> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vec.into_iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a AtLeast<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AtLeast;
+    use crate::{nonempty_vec, HeadLast, NonemptyVec};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn polygon() {
+        let mut p = AtLeast::new([(0, 0), (1, 0), (0, 1)]);
+        assert_eq!(p.get_const::<2>(), &(0, 1));
+        assert_eq!((p.first(), p.last()), (&(0, 0), &(0, 1)));
+        p.insert(1, (5, 5));
+        assert_eq!(p.remove(0), Some((0, 0)));
+        assert_eq!(p.prefix(), &[(5, 5), (1, 0), (0, 1)]);
+        assert_eq!(p.remove(1), None);
+        p.push((2, 2));
+        p.truncate(0);
+        assert_eq!(p.len(), 3);
+        assert!(AtLeast::<u8, 2>::try_from(vec![1]).is_err());
+        let q = AtLeast::<_, 2>::try_from(vec![1, 2, 3]).unwrap();
+        assert_eq!(q.rest(), [3]);
+        assert_eq!(q.into_iter().collect::<Vec<_>>(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn nonempty_vec() {
+        let mut v = AtLeast::from(nonempty_vec![1, 2, 3]);
+        assert_eq!(v.prefix(), &[1]);
+        v.reserve(10);
+        let capacity = v.capacity();
+        let w: NonemptyVec<_, HeadLast> = v.into();
+        assert_eq!(*w.head(), 3);
+        assert_eq!(w.capacity().get(), capacity);
+        let v = AtLeast::from(w);
+        assert_eq!(v.as_slice(), [1, 2, 3]);
+        assert_eq!(v.capacity(), capacity);
+    }
+
+    #[test]
+    fn slice_view() {
+        let mut v = AtLeast::<_, 2>::try_from(vec![3, 1, 2]).unwrap();
+        v.rest_mut()[0] = 4;
+        v.prefix_mut().swap(0, 1);
+        assert_eq!(v.as_ref(), [1, 3, 4]);
+        assert!(v < AtLeast::new([1, 4]));
+        let (prefix, rest) = v.into_prefix_rest();
+        assert_eq!((prefix, rest), ([1, 3], vec![4]));
+    }
+}
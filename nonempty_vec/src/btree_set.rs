@@ -0,0 +1,175 @@
+//! The [`NonemptyBTreeSet`](struct.NonemptyBTreeSet.html) type
+
+use crate::iter::{FromNonemptyIterator, Nonempty, NonemptyIterator};
+use alloc::collections::{btree_set, BTreeSet};
+use core::borrow::Borrow;
+use core::convert::TryFrom;
+use core::num::NonZeroUsize;
+
+/// A `BTreeSet` that always has at least one element
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct NonemptyBTreeSet<T>(BTreeSet<T>);
+
+#[allow(clippy::len_without_is_empty)]
+impl<T: Ord> NonemptyBTreeSet<T> {
+    /// Creates a new `NonemptyBTreeSet` with one element
+    pub fn new(t: T) -> Self {
+        let mut set = BTreeSet::new();
+        set.insert(t);
+        Self(set)
+    }
+
+    /// Returns the underlying set
+    pub fn as_set(&self) -> &BTreeSet<T> {
+        &self.0
+    }
+
+    /// Consumes `self` and returns the underlying set
+    pub fn into_set(self) -> BTreeSet<T> {
+        self.0
+    }
+
+    /// Returns the number of elements in `self`
+    pub fn len(&self) -> NonZeroUsize {
+        NonZeroUsize::new(self.0.len()).unwrap()
+    }
+
+    /// The smallest element
+    pub fn first(&self) -> &T {
+        self.0.iter().next().unwrap()
+    }
+
+    /// The largest element
+    pub fn last(&self) -> &T {
+        self.0.iter().next_back().unwrap()
+    }
+
+    /// Returns whether `self` contains `value`
+    pub fn contains<Q: Ord + ?Sized>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+    {
+        self.0.contains(value)
+    }
+
+    /// Adds a value to `self`, returns whether it was newly inserted
+    pub fn insert(&mut self, t: T) -> bool {
+        self.0.insert(t)
+    }
+
+    /// Removes a value from `self`, returns whether it was present and removed
+    ///
+    /// The last remaining value is never removed.
+    pub fn remove<Q: Ord + ?Sized>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+    {
+        self.0.len() > 1 && self.0.remove(value)
+    }
+
+    /// Removes the smallest element from `self`
+    /// (unless `self` has only one item left), returns it.
+    pub fn pop_first(&mut self) -> Option<T> {
+        if self.0.len() > 1 {
+            self.0.pop_first()
+        } else {
+            None
+        }
+    }
+
+    /// Removes the largest element from `self`
+    /// (unless `self` has only one item left), returns it.
+    pub fn pop_last(&mut self) -> Option<T> {
+        if self.0.len() > 1 {
+            self.0.pop_last()
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the elements in ascending order
+    pub fn iter(&self) -> btree_set::Iter<'_, T> {
+        self.0.iter()
+    }
+
+    /// Like `iter`, but returns a [`NonemptyIterator`](iter/trait.NonemptyIterator.html)
+    pub fn nonempty_iter(&self) -> Nonempty<btree_set::Iter<'_, T>> {
+        Nonempty { iter: self.iter() }
+    }
+
+    /// Like `into_iter`, but returns a [`NonemptyIterator`](iter/trait.NonemptyIterator.html)
+    pub fn into_nonempty_iter(self) -> Nonempty<btree_set::IntoIter<T>> {
+        Nonempty {
+            iter: self.into_iter(),
+        }
+    }
+}
+
+impl<T> IntoIterator for NonemptyBTreeSet<T> {
+    type Item = T;
+    type IntoIter = btree_set::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a NonemptyBTreeSet<T> {
+    type Item = &'a T;
+    type IntoIter = btree_set::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T: Ord> Extend<T> for NonemptyBTreeSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+impl<T: Ord> FromNonemptyIterator<T> for NonemptyBTreeSet<T> {
+    fn from_nonempty_iter<I: NonemptyIterator<Item = T>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<T> TryFrom<BTreeSet<T>> for NonemptyBTreeSet<T> {
+    /// The original set is returned if it is empty
+    type Error = BTreeSet<T>;
+
+    fn try_from(set: BTreeSet<T>) -> Result<Self, BTreeSet<T>> {
+        if set.is_empty() {
+            Err(set)
+        } else {
+            Ok(Self(set))
+        }
+    }
+}
+
+impl<T> From<NonemptyBTreeSet<T>> for BTreeSet<T> {
+    fn from(set: NonemptyBTreeSet<T>) -> Self {
+        set.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NonemptyBTreeSet;
+    use crate::{nonempty_vec, NonemptyIterator};
+
+    #[test]
+    fn set() {
+        let mut s: NonemptyBTreeSet<_> = nonempty_vec![3, 1, 2, 1].into_nonempty_iter().collect();
+        assert_eq!(s.len().get(), 3);
+        assert_eq!((*s.first(), *s.last()), (1, 3));
+        assert_eq!(s.iter().len(), 3);
+        assert_eq!(s.nonempty_iter().map(|x| x * 2).max(), 6);
+        assert!(s.remove(&2));
+        assert_eq!(s.pop_first(), Some(1));
+        assert_eq!(s.pop_last(), None);
+        assert!(!s.remove(&3));
+        assert!(s.contains(&3));
+    }
+}
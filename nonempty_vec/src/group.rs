@@ -0,0 +1,206 @@
+//! Iterator adaptors that split an iterator into [`NonemptyVec`](../struct.NonemptyVec.html)s
+//!
+//! Every group yielded by these adaptors is nonempty by construction,
+//! so they are yielded as `NonemptyVec`s instead of `Vec`s.
+
+use crate::NonemptyVec;
+use core::iter::FusedIterator;
+
+/// Extension methods for grouping the items of an `Iterator`
+pub trait NonemptyGroups: Iterator + Sized {
+    /// Groups consecutive items that map to the same key,
+    /// yielding each key together with its group
+    fn group_by_key<K: PartialEq, F: FnMut(&Self::Item) -> K>(
+        self,
+        key: F,
+    ) -> GroupByKey<Self, K, F> {
+        GroupByKey {
+            iter: self,
+            key,
+            pending: None,
+        }
+    }
+
+    /// Groups consecutive items, as long as `same_group` returns `true`
+    /// for every pair of neighbouring items
+    fn chunk_by<F: FnMut(&Self::Item, &Self::Item) -> bool>(
+        self,
+        same_group: F,
+    ) -> ChunkBy<Self, F> {
+        ChunkBy {
+            iter: self,
+            same_group,
+            pending: None,
+        }
+    }
+
+    /// Starts a new group every time `split` returns `true`
+    /// for a pair of neighbouring items
+    ///
+    /// This is the opposite of [`chunk_by`](#method.chunk_by)
+    fn split_when<F: FnMut(&Self::Item, &Self::Item) -> bool>(
+        self,
+        split: F,
+    ) -> SplitWhen<Self, F> {
+        SplitWhen {
+            iter: self,
+            split,
+            pending: None,
+        }
+    }
+
+    /// Groups the items into chunks of `size` items,
+    /// the last chunk may be shorter
+    ///
+    /// # Panics
+    /// Panics if `size` is `0`
+    fn chunks(self, size: usize) -> Chunks<Self> {
+        assert_ne!(size, 0, "chunk size must be nonzero");
+        Chunks { iter: self, size }
+    }
+}
+
+impl<I: Iterator> NonemptyGroups for I {}
+
+/// Collects a group starting with `first`,
+/// leaving the first item that doesn't belong in it in `pending`
+fn next_group<I: Iterator, F: FnMut(&I::Item, &I::Item) -> bool>(
+    iter: &mut I,
+    pending: &mut Option<I::Item>,
+    mut same_group: F,
+) -> Option<NonemptyVec<I::Item>> {
+    let first = pending.take().or_else(|| iter.next())?;
+    let mut group = NonemptyVec::new(first);
+    for t in iter {
+        if same_group(group.last(), &t) {
+            group.push(t);
+        } else {
+            *pending = Some(t);
+            break;
+        }
+    }
+    Some(group)
+}
+
+/// The [`group_by_key`](trait.NonemptyGroups.html#method.group_by_key) adaptor
+pub struct GroupByKey<I: Iterator, K, F> {
+    iter: I,
+    key: F,
+    pending: Option<(K, I::Item)>,
+}
+
+impl<I: Iterator, K: PartialEq, F: FnMut(&I::Item) -> K> Iterator for GroupByKey<I, K, F> {
+    type Item = (K, NonemptyVec<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Self { iter, key, pending } = self;
+        let (k, first) = pending
+            .take()
+            .or_else(|| iter.next().map(|t| (key(&t), t)))?;
+        let mut group = NonemptyVec::new(first);
+        for t in iter {
+            let next_key = key(&t);
+            if next_key == k {
+                group.push(t);
+            } else {
+                *pending = Some((next_key, t));
+                break;
+            }
+        }
+        Some((k, group))
+    }
+}
+
+impl<I: FusedIterator, K: PartialEq, F: FnMut(&I::Item) -> K> FusedIterator
+    for GroupByKey<I, K, F>
+{
+}
+
+/// The [`chunk_by`](trait.NonemptyGroups.html#method.chunk_by) adaptor
+pub struct ChunkBy<I: Iterator, F> {
+    iter: I,
+    same_group: F,
+    pending: Option<I::Item>,
+}
+
+impl<I: Iterator, F: FnMut(&I::Item, &I::Item) -> bool> Iterator for ChunkBy<I, F> {
+    type Item = NonemptyVec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_group(&mut self.iter, &mut self.pending, &mut self.same_group)
+    }
+}
+
+impl<I: FusedIterator, F: FnMut(&I::Item, &I::Item) -> bool> FusedIterator for ChunkBy<I, F> {}
+
+/// The [`split_when`](trait.NonemptyGroups.html#method.split_when) adaptor
+pub struct SplitWhen<I: Iterator, F> {
+    iter: I,
+    split: F,
+    pending: Option<I::Item>,
+}
+
+impl<I: Iterator, F: FnMut(&I::Item, &I::Item) -> bool> Iterator for SplitWhen<I, F> {
+    type Item = NonemptyVec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let split = &mut self.split;
+        next_group(&mut self.iter, &mut self.pending, |a, b| !split(a, b))
+    }
+}
+
+impl<I: FusedIterator, F: FnMut(&I::Item, &I::Item) -> bool> FusedIterator for SplitWhen<I, F> {}
+
+/// The [`chunks`](trait.NonemptyGroups.html#method.chunks) adaptor
+pub struct Chunks<I> {
+    iter: I,
+    size: usize,
+}
+
+impl<I: Iterator> Iterator for Chunks<I> {
+    type Item = NonemptyVec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = NonemptyVec::new(self.iter.next()?);
+        chunk.extend(self.iter.by_ref().take(self.size - 1));
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.iter.size_hint();
+        (lo.div_ceil(self.size), hi.map(|n| n.div_ceil(self.size)))
+    }
+}
+
+impl<I: FusedIterator> FusedIterator for Chunks<I> {}
+
+#[cfg(test)]
+mod tests {
+    use super::NonemptyGroups;
+
+    fn groups<I: Iterator<Item = crate::NonemptyVec<i32>>>(iter: I) -> Vec<Vec<i32>> {
+        iter.map(|g| g.into_vec()).collect()
+    }
+
+    #[test]
+    fn grouping() {
+        let data = [1, 3, 2, 4, 4, 5];
+        let by_parity: Vec<_> = data
+            .iter()
+            .group_by_key(|x| *x % 2)
+            .map(|(k, g)| (k, g.into_vec()))
+            .collect();
+        assert_eq!(
+            by_parity,
+            [(1, vec![&1, &3]), (0, vec![&2, &4, &4]), (1, vec![&5])]
+        );
+        let ascending = groups(data.iter().copied().chunk_by(|a, b| a < b));
+        assert_eq!(ascending, [vec![1, 3], vec![2, 4], vec![4, 5]]);
+        let split = groups(data.iter().copied().split_when(|a, b| a == b));
+        assert_eq!(split, [vec![1, 3, 2, 4], vec![4, 5]]);
+        let chunks = data.iter().copied().chunks(4);
+        assert_eq!(chunks.size_hint(), (2, Some(2)));
+        assert_eq!(groups(chunks), [vec![1, 3, 2, 4], vec![4, 5]]);
+        assert_eq!(groups(None.into_iter().chunks(2)), Vec::<Vec<i32>>::new());
+    }
+}
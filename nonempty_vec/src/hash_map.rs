@@ -0,0 +1,209 @@
+//! The [`NonemptyHashMap`](struct.NonemptyHashMap.html) type
+
+use crate::iter::{FromNonemptyIterator, Nonempty, NonemptyIterator};
+use core::borrow::Borrow;
+use core::convert::TryFrom;
+use core::hash::{BuildHasher, Hash};
+use core::num::NonZeroUsize;
+use std::collections::hash_map::{self, HashMap, RandomState};
+
+/// A `HashMap` that always has at least one entry
+#[derive(Debug, Clone)]
+pub struct NonemptyHashMap<K, V, S = RandomState>(HashMap<K, V, S>);
+
+impl<K: Eq + Hash, V> NonemptyHashMap<K, V> {
+    /// Creates a new `NonemptyHashMap` with one entry
+    pub fn new(key: K, value: V) -> Self {
+        let mut map = HashMap::new();
+        map.insert(key, value);
+        Self(map)
+    }
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl<K: Eq + Hash, V, S: BuildHasher> NonemptyHashMap<K, V, S> {
+    /// Returns the underlying map
+    pub fn as_map(&self) -> &HashMap<K, V, S> {
+        &self.0
+    }
+
+    /// Consumes `self` and returns the underlying map
+    pub fn into_map(self) -> HashMap<K, V, S> {
+        self.0
+    }
+
+    /// Returns the number of entries in `self`
+    pub fn len(&self) -> NonZeroUsize {
+        NonZeroUsize::new(self.0.len()).unwrap()
+    }
+
+    /// An arbitrary entry, namely the one that iteration starts with
+    pub fn first(&self) -> (&K, &V) {
+        self.0.iter().next().unwrap()
+    }
+
+    /// Returns a reference to the value corresponding to `key`
+    pub fn get<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        self.0.get(key)
+    }
+
+    /// Like `get` but mutable
+    pub fn get_mut<Q: Eq + Hash + ?Sized>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+    {
+        self.0.get_mut(key)
+    }
+
+    /// Returns whether `self` contains an entry for `key`
+    pub fn contains_key<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.0.contains_key(key)
+    }
+
+    /// Inserts an entry, returning the previous value for `key`
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.0.insert(key, value)
+    }
+
+    /// Removes the entry for `key`, returning its value
+    ///
+    /// Returns `None` if there is no such entry or if it is the only one left.
+    pub fn remove<Q: Eq + Hash + ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        if self.0.len() > 1 {
+            self.0.remove(key)
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the entries
+    pub fn iter(&self) -> hash_map::Iter<'_, K, V> {
+        self.0.iter()
+    }
+
+    /// Returns an iterator over the keys
+    pub fn keys(&self) -> hash_map::Keys<'_, K, V> {
+        self.0.keys()
+    }
+
+    /// Returns an iterator over the values
+    pub fn values(&self) -> hash_map::Values<'_, K, V> {
+        self.0.values()
+    }
+
+    /// Like `iter`, but returns a [`NonemptyIterator`](iter/trait.NonemptyIterator.html)
+    pub fn nonempty_iter(&self) -> Nonempty<hash_map::Iter<'_, K, V>> {
+        Nonempty { iter: self.iter() }
+    }
+
+    /// Like `keys`, but returns a [`NonemptyIterator`](iter/trait.NonemptyIterator.html)
+    pub fn nonempty_keys(&self) -> Nonempty<hash_map::Keys<'_, K, V>> {
+        Nonempty { iter: self.keys() }
+    }
+
+    /// Like `values`, but returns a [`NonemptyIterator`](iter/trait.NonemptyIterator.html)
+    pub fn nonempty_values(&self) -> Nonempty<hash_map::Values<'_, K, V>> {
+        Nonempty {
+            iter: self.values(),
+        }
+    }
+
+    /// Like `into_iter`, but returns a [`NonemptyIterator`](iter/trait.NonemptyIterator.html)
+    pub fn into_nonempty_iter(self) -> Nonempty<hash_map::IntoIter<K, V>> {
+        Nonempty {
+            iter: self.into_iter(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: PartialEq, S: BuildHasher> PartialEq for NonemptyHashMap<K, V, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<K: Eq + Hash, V: Eq, S: BuildHasher> Eq for NonemptyHashMap<K, V, S> {}
+
+impl<K, V, S> IntoIterator for NonemptyHashMap<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = hash_map::IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a NonemptyHashMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = hash_map::Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<K: Eq + Hash, V, S: BuildHasher> Extend<(K, V)> for NonemptyHashMap<K, V, S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+impl<K: Eq + Hash, V, S: BuildHasher + Default> FromNonemptyIterator<(K, V)>
+    for NonemptyHashMap<K, V, S>
+{
+    fn from_nonempty_iter<I: NonemptyIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<K, V, S> TryFrom<HashMap<K, V, S>> for NonemptyHashMap<K, V, S> {
+    /// The original map is returned if it is empty
+    type Error = HashMap<K, V, S>;
+
+    fn try_from(map: HashMap<K, V, S>) -> Result<Self, HashMap<K, V, S>> {
+        if map.is_empty() {
+            Err(map)
+        } else {
+            Ok(Self(map))
+        }
+    }
+}
+
+impl<K, V, S> From<NonemptyHashMap<K, V, S>> for HashMap<K, V, S> {
+    fn from(map: NonemptyHashMap<K, V, S>) -> Self {
+        map.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NonemptyHashMap;
+    use crate::NonemptyIterator;
+    use std::collections::HashMap;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn map() {
+        assert!(NonemptyHashMap::try_from(HashMap::<u8, u8>::new()).is_err());
+        let mut m = NonemptyHashMap::new("a", 1);
+        m.insert("b", 3);
+        m.insert("c", 2);
+        assert_eq!(*m.nonempty_values().max(), 3);
+        assert_eq!(m.nonempty_iter().max_by_key(|(_, v)| **v).0, &"b");
+        assert_eq!(m.keys().len(), 3);
+        assert_eq!(m.remove("b"), Some(3));
+        assert_eq!(m.remove("c"), Some(2));
+        assert_eq!(m.remove("a"), None);
+        assert_eq!(m.first(), (&"a", &1));
+        assert_eq!(m.len().get(), 1);
+    }
+}
@@ -0,0 +1,428 @@
+//! Iterators over the elements of a [`NonemptyVec`](../struct.NonemptyVec.html)
+//!
+//! All iterators in this module visit the elements in logical order,
+//! regardless of the `HeadLocation`.
+//!
+//! It also contains the [`NonemptyIterator`](trait.NonemptyIterator.html) trait
+//! and its adaptors, which keep track of the fact that there is at least one item.
+
+use crate::{HeadFirst, HeadLocation, NonemptyVec};
+use alloc::vec;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::slice;
+
+/// Generates an iterator that yields the head and the body in logical order
+macro_rules! head_body_iter {
+    ($(#[$m:meta])* $name:ident<$($lt:lifetime,)? $t:ident>: $head:ty, $body:ty => $item:ty) => {
+        $(#[$m])*
+        pub struct $name<$($lt,)? $t, H: $crate::HeadLocation = $crate::HeadFirst> {
+            pub(crate) head: Option<$head>,
+            pub(crate) body: $body,
+            pub(crate) _marker: ::core::marker::PhantomData<H>,
+        }
+
+        impl<$($lt,)? $t, H: $crate::HeadLocation> Iterator for $name<$($lt,)? $t, H> {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if H::HEAD_FIRST {
+                    self.head.take().or_else(|| self.body.next())
+                } else {
+                    self.body.next().or_else(|| self.head.take())
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.len();
+                (len, Some(len))
+            }
+        }
+
+        impl<$($lt,)? $t, H: $crate::HeadLocation> DoubleEndedIterator for $name<$($lt,)? $t, H> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                if H::HEAD_FIRST {
+                    self.body.next_back().or_else(|| self.head.take())
+                } else {
+                    self.head.take().or_else(|| self.body.next_back())
+                }
+            }
+        }
+
+        impl<$($lt,)? $t, H: $crate::HeadLocation> ExactSizeIterator for $name<$($lt,)? $t, H> {
+            fn len(&self) -> usize {
+                self.body.len() + self.head.is_some() as usize
+            }
+        }
+
+        impl<$($lt,)? $t, H: $crate::HeadLocation> ::core::iter::FusedIterator for $name<$($lt,)? $t, H> {}
+    };
+}
+
+/// Generates an iterator over a `NonemptyVec` that wraps the iterator of the underlying `Vec`,
+/// since the elements are stored in logical order
+macro_rules! vec_iter {
+    ($(#[$m:meta])* $name:ident<$($lt:lifetime,)? $t:ident>: $inner:ty => $item:ty) => {
+        $(#[$m])*
+        pub struct $name<$($lt,)? $t, H: HeadLocation = HeadFirst> {
+            pub(crate) iter: $inner,
+            pub(crate) _marker: PhantomData<H>,
+        }
+
+        impl<$($lt,)? $t, H: HeadLocation> Iterator for $name<$($lt,)? $t, H> {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.iter.next()
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iter.size_hint()
+            }
+        }
+
+        impl<$($lt,)? $t, H: HeadLocation> DoubleEndedIterator for $name<$($lt,)? $t, H> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.iter.next_back()
+            }
+        }
+
+        impl<$($lt,)? $t, H: HeadLocation> ExactSizeIterator for $name<$($lt,)? $t, H> {
+            fn len(&self) -> usize {
+                self.iter.len()
+            }
+        }
+
+        impl<$($lt,)? $t, H: HeadLocation> FusedIterator for $name<$($lt,)? $t, H> {}
+    };
+}
+
+vec_iter! {
+    /// An iterator over references to the elements of a `NonemptyVec`
+    Iter<'a, T>: slice::Iter<'a, T> => &'a T
+}
+
+vec_iter! {
+    /// An iterator over mutable references to the elements of a `NonemptyVec`
+    IterMut<'a, T>: slice::IterMut<'a, T> => &'a mut T
+}
+
+vec_iter! {
+    /// An iterator that moves the elements out of a `NonemptyVec`
+    IntoIter<T>: vec::IntoIter<T> => T
+}
+
+impl<'a, T, H: HeadLocation> Clone for Iter<'a, T, H> {
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+const EMPTY: &str = "NonemptyIterator yielded no items";
+
+/// An iterator that is guaranteed to yield at least one item
+///
+/// This is built on top of `IntoIterator` rather than `Iterator`,
+/// so that the total methods provided here don't clash with their
+/// `Option`-returning counterparts from `Iterator`.
+/// Use `into_iter` to get a regular iterator back.
+///
+/// This trait is sealed: it is only implemented by the iterators of this crate,
+/// which are known to yield at least one item,
+/// so other code can rely on that (and the methods here never panic).
+pub trait NonemptyIterator: IntoIterator + Sized + self::private::Sealed {
+    /// Returns the first item and an iterator over the rest
+    fn split_first(self) -> (Self::Item, Self::IntoIter) {
+        let mut iter = self.into_iter();
+        let first = iter.next().expect(EMPTY);
+        (first, iter)
+    }
+
+    /// Returns the first item
+    fn first(self) -> Self::Item {
+        self.split_first().0
+    }
+
+    /// Returns the last item
+    fn last(self) -> Self::Item {
+        self.into_iter().last().expect(EMPTY)
+    }
+
+    /// Like `Iterator::reduce`, but total
+    fn reduce<F: FnMut(Self::Item, Self::Item) -> Self::Item>(self, f: F) -> Self::Item {
+        let (first, rest) = self.split_first();
+        rest.fold(first, f)
+    }
+
+    /// Like `Iterator::max`, but total
+    fn max(self) -> Self::Item
+    where
+        Self::Item: Ord,
+    {
+        self.into_iter().max().expect(EMPTY)
+    }
+
+    /// Like `Iterator::min`, but total
+    fn min(self) -> Self::Item
+    where
+        Self::Item: Ord,
+    {
+        self.into_iter().min().expect(EMPTY)
+    }
+
+    /// Like `Iterator::max_by_key`, but total
+    fn max_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Self::Item {
+        self.into_iter().max_by_key(f).expect(EMPTY)
+    }
+
+    /// Like `Iterator::min_by_key`, but total
+    fn min_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Self::Item {
+        self.into_iter().min_by_key(f).expect(EMPTY)
+    }
+
+    /// Like `Iterator::map`
+    fn map<B, F: FnMut(Self::Item) -> B>(self, f: F) -> Map<Self, F> {
+        Map { iter: self, f }
+    }
+
+    /// Like `Iterator::zip`, the result is nonempty if both sides are
+    fn zip<U: NonemptyIterator>(self, other: U) -> Zip<Self, U> {
+        Zip { a: self, b: other }
+    }
+
+    /// Like `Iterator::enumerate`
+    fn enumerate(self) -> Enumerate<Self> {
+        Enumerate { iter: self }
+    }
+
+    /// Like `Iterator::chain`, the other side may be empty
+    fn chain<U: IntoIterator<Item = Self::Item>>(self, other: U) -> Chain<Self, U> {
+        Chain { a: self, b: other }
+    }
+
+    /// Like `Iterator::rev`
+    fn rev(self) -> Rev<Self>
+    where
+        Self::IntoIter: DoubleEndedIterator,
+    {
+        Rev { iter: self }
+    }
+
+    /// Like `Iterator::collect`, but into a collection that cannot be empty
+    fn collect<B: FromNonemptyIterator<Self::Item>>(self) -> B {
+        B::from_nonempty_iter(self)
+    }
+}
+
+/// Conversion from a [`NonemptyIterator`](trait.NonemptyIterator.html)
+pub trait FromNonemptyIterator<T>: Sized {
+    /// Creates a value from a `NonemptyIterator`
+    fn from_nonempty_iter<I: NonemptyIterator<Item = T>>(iter: I) -> Self;
+}
+
+impl<T, H: HeadLocation> FromNonemptyIterator<T> for NonemptyVec<T, H> {
+    fn from_nonempty_iter<I: NonemptyIterator<Item = T>>(iter: I) -> Self {
+        let (first, rest) = iter.split_first();
+        let mut res = NonemptyVec::new(first);
+        res.extend(rest);
+        res
+    }
+}
+
+/// A regular iterator that is known to yield at least one item
+///
+/// This is what [`NonemptyVec::iter`](../struct.NonemptyVec.html#method.iter) and friends return.
+#[derive(Clone)]
+pub struct Nonempty<I> {
+    pub(crate) iter: I,
+}
+
+impl<I: Iterator> IntoIterator for Nonempty<I> {
+    type Item = I::Item;
+    type IntoIter = I;
+
+    fn into_iter(self) -> I {
+        self.iter
+    }
+}
+
+impl<I: Iterator> NonemptyIterator for Nonempty<I> {}
+impl<I: Iterator> private::Sealed for Nonempty<I> {}
+
+/// The [`map`](trait.NonemptyIterator.html#method.map) adaptor
+#[derive(Clone)]
+pub struct Map<I, F> {
+    iter: I,
+    f: F,
+}
+
+impl<B, I: NonemptyIterator, F: FnMut(I::Item) -> B> IntoIterator for Map<I, F> {
+    type Item = B;
+    type IntoIter = core::iter::Map<I::IntoIter, F>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter.into_iter().map(self.f)
+    }
+}
+
+impl<B, I: NonemptyIterator, F: FnMut(I::Item) -> B> NonemptyIterator for Map<I, F> {}
+impl<B, I: NonemptyIterator, F: FnMut(I::Item) -> B> private::Sealed for Map<I, F> {}
+
+/// The [`zip`](trait.NonemptyIterator.html#method.zip) adaptor
+#[derive(Clone)]
+pub struct Zip<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A: NonemptyIterator, B: NonemptyIterator> IntoIterator for Zip<A, B> {
+    type Item = (A::Item, B::Item);
+    type IntoIter = core::iter::Zip<A::IntoIter, B::IntoIter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.a.into_iter().zip(self.b)
+    }
+}
+
+impl<A: NonemptyIterator, B: NonemptyIterator> NonemptyIterator for Zip<A, B> {}
+impl<A: NonemptyIterator, B: NonemptyIterator> private::Sealed for Zip<A, B> {}
+
+/// The [`enumerate`](trait.NonemptyIterator.html#method.enumerate) adaptor
+#[derive(Clone)]
+pub struct Enumerate<I> {
+    iter: I,
+}
+
+impl<I: NonemptyIterator> IntoIterator for Enumerate<I> {
+    type Item = (usize, I::Item);
+    type IntoIter = core::iter::Enumerate<I::IntoIter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter.into_iter().enumerate()
+    }
+}
+
+impl<I: NonemptyIterator> NonemptyIterator for Enumerate<I> {}
+impl<I: NonemptyIterator> private::Sealed for Enumerate<I> {}
+
+/// The [`chain`](trait.NonemptyIterator.html#method.chain) adaptor
+#[derive(Clone)]
+pub struct Chain<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A: NonemptyIterator, B: IntoIterator<Item = A::Item>> IntoIterator for Chain<A, B> {
+    type Item = A::Item;
+    type IntoIter = core::iter::Chain<A::IntoIter, B::IntoIter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.a.into_iter().chain(self.b)
+    }
+}
+
+impl<A: NonemptyIterator, B: IntoIterator<Item = A::Item>> NonemptyIterator for Chain<A, B> {}
+impl<A: NonemptyIterator, B: IntoIterator<Item = A::Item>> private::Sealed for Chain<A, B> {}
+
+/// The [`rev`](trait.NonemptyIterator.html#method.rev) adaptor
+#[derive(Clone)]
+pub struct Rev<I> {
+    iter: I,
+}
+
+impl<I: NonemptyIterator> IntoIterator for Rev<I>
+where
+    I::IntoIter: DoubleEndedIterator,
+{
+    type Item = I::Item;
+    type IntoIter = core::iter::Rev<I::IntoIter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter.into_iter().rev()
+    }
+}
+
+impl<I: NonemptyIterator> NonemptyIterator for Rev<I> where I::IntoIter: DoubleEndedIterator {}
+impl<I: NonemptyIterator> private::Sealed for Rev<I> where I::IntoIter: DoubleEndedIterator {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HeadFirst, HeadLast, NonemptyIterator, NonemptyVec};
+
+    fn filled<H: crate::HeadLocation>() -> NonemptyVec<i32, H> {
+        let mut v = NonemptyVec::new(1);
+        v.push(2);
+        v.push(3);
+        v
+    }
+
+    #[test]
+    fn logical_order() {
+        let first = filled::<HeadFirst>();
+        let last = filled::<HeadLast>();
+        assert_eq!(first.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(last.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(first.into_iter().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(last.into_iter().collect::<Vec<_>>(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn double_ended() {
+        let last = filled::<HeadLast>();
+        let mut iter = last.iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next_back(), Some(&2));
+        assert_eq!(iter.next(), None);
+        assert_eq!(last.iter().filter(|x| **x > 1).count(), 2);
+
+        let first = filled::<HeadFirst>();
+        assert_eq!(first.into_iter().rev().collect::<Vec<_>>(), [3, 2, 1]);
+    }
+
+    #[test]
+    fn mutate() {
+        let mut v = filled::<HeadLast>();
+        for (i, x) in v.iter_mut().enumerate() {
+            *x *= i as i32;
+        }
+        assert_eq!(v.into_vec(), [0, 2, 6]);
+    }
+
+    #[test]
+    fn total_reductions() {
+        let v = filled::<HeadLast>();
+        assert_eq!(*v.nonempty_iter().max(), 3);
+        assert_eq!(*v.nonempty_iter().min(), 1);
+        assert_eq!(*v.nonempty_iter().first(), 1);
+        assert_eq!(*v.nonempty_iter().last(), 3);
+        assert_eq!(v.nonempty_iter().map(|x| x % 3).max_by_key(|x| *x), 2);
+        assert_eq!(v.nonempty_iter().map(|x| *x).reduce(|a, b| a * 10 + b), 123);
+        assert_eq!(*NonemptyVec::<i32>::new(5).nonempty_iter().max(), 5);
+    }
+
+    #[test]
+    fn adaptors() {
+        let v = filled::<HeadFirst>();
+        let w = filled::<HeadLast>();
+        let zipped: NonemptyVec<_, HeadLast> = v
+            .nonempty_iter()
+            .zip(w.nonempty_iter().rev())
+            .map(|(a, b)| a * b)
+            .chain(vec![7])
+            .collect();
+        assert_eq!(zipped.into_vec(), [3, 4, 3, 7]);
+
+        let e: NonemptyVec<_> = v.into_nonempty_iter().enumerate().rev().collect();
+        assert_eq!(e.into_vec(), [(2, 3), (1, 2), (0, 1)]);
+    }
+}
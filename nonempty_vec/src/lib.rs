@@ -1,8 +1,83 @@
-//! This crate provides a `Vec`-like struct that cannot be empty
+//! This crate provides a `Vec`-like struct that cannot be empty,
+//! as well as some other collections with the same guarantee
+//!
+//! The crate is `no_std` and only needs `alloc`, unless the `use-std` feature is enabled.
+//!
+//! # Features
+//! - `use-std` (default): [`NonemptyHashMap`](hash_map/struct.NonemptyHashMap.html),
+//!   which needs `std`
+//! - `serde`: `Serialize` and `Deserialize` for `NonemptyVec`,
+//!   which rejects empty sequences when deserializing
 
-use std::marker::PhantomData;
-use std::mem;
-use std::num::NonZeroUsize;
+#![cfg_attr(not(any(test, feature = "use-std")), no_std)]
+extern crate alloc;
+
+use alloc::vec::{Drain, Vec};
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::Hash;
+use core::marker::PhantomData;
+use core::num::NonZeroUsize;
+use core::ops::{Bound, Index, IndexMut, RangeBounds};
+
+#[macro_use]
+pub mod iter;
+pub mod at_least;
+pub mod btree_set;
+pub mod group;
+#[cfg(feature = "use-std")]
+pub mod hash_map;
+#[cfg(feature = "serde")]
+mod serde_impls;
+pub mod slice;
+pub mod string;
+pub mod vec_deque;
+
+pub use at_least::AtLeast;
+pub use btree_set::NonemptyBTreeSet;
+pub use group::NonemptyGroups;
+#[cfg(feature = "use-std")]
+pub use hash_map::NonemptyHashMap;
+pub use iter::{FromNonemptyIterator, NonemptyIterator};
+pub use slice::NonemptySlice;
+pub use string::NonemptyString;
+pub use vec_deque::NonemptyVecDeque;
+
+#[doc(hidden)]
+pub use alloc::vec as __vec;
+
+/// Creates a [`NonemptyVec`](struct.NonemptyVec.html) (with `HeadFirst`) containing the arguments
+///
+/// Just like `vec!`, but an empty list is rejected at compile time.
+///
+/// # Example
+/// ```
+/// # use nonempty_vec::nonempty_vec;
+/// let v = nonempty_vec![1, 2, 3];
+/// assert_eq!(*v.head(), 1);
+/// assert_eq!(v.tail(), [2, 3]);
+/// ```
+///
+/// ```compile_fail
+/// # use nonempty_vec::nonempty_vec;
+/// let v: nonempty_vec::NonemptyVec<i32> = nonempty_vec![];
+/// ```
+#[macro_export]
+macro_rules! nonempty_vec {
+    () => {
+        compile_error!("a `NonemptyVec` needs at least one element")
+    };
+    ($head:expr $(, $tail:expr)* $(,)?) => {
+        match <$crate::NonemptyVec<_, $crate::HeadFirst> as ::core::convert::TryFrom<_>>::try_from(
+            $crate::__vec![$head $(, $tail)*],
+        ) {
+            ::core::result::Result::Ok(v) => v,
+            ::core::result::Result::Err(_) => ::core::unreachable!(),
+        }
+    };
+}
 
 mod private {
     use super::{HeadFirst, HeadLast};
@@ -13,234 +88,784 @@ mod private {
 }
 
 /// Specifies the location the head item has in relation to the rest
-pub trait HeadLocation: self::private::Sealed {
+///
+/// The supertraits let the derived impls of types that are generic
+/// over `HeadLocation` apply in generic code as well.
+pub trait HeadLocation: self::private::Sealed + Copy + Eq + Ord + Hash + fmt::Debug {
     /// This constant can be used to write code generic over `HeadLocation`
     /// without explicit casing
     const HEAD_FIRST: bool;
+
+    /// The other `HeadLocation`
+    type Opposite: HeadLocation<Opposite = Self>;
 }
 
 /// The head item is in front of the rest (at index `0`)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum HeadFirst {}
 /// The head item is after the rest (at index `len-1`)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum HeadLast {}
 
 impl HeadLocation for HeadFirst {
     const HEAD_FIRST: bool = true;
+    type Opposite = HeadLast;
 }
 impl HeadLocation for HeadLast {
     const HEAD_FIRST: bool = false;
+    type Opposite = HeadFirst;
 }
 
 /// A `Vec` that always has at least one element
-#[derive(Default, Debug, Clone, Eq, PartialEq)]
+///
+/// The elements are stored contiguously in logical order,
+/// the `HeadLocation` only decides which end the head is at.
+/// This means `NonemptyVec` can be viewed as a `[T]` with `AsRef` and `Borrow`,
+/// and `Eq`, `Ord` and `Hash` agree with those of the slice.
+///
+/// It is built on [`AtLeast<T, 1>`](at_least/struct.AtLeast.html),
+/// which it can be converted to and from for free.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct NonemptyVec<T, H: HeadLocation = HeadFirst> {
-    head: T,
-    body: Vec<T>,
+    inner: AtLeast<T, 1>,
     _marker: PhantomData<H>,
 }
 
 #[allow(clippy::len_without_is_empty)]
 impl<T, H: HeadLocation> NonemptyVec<T, H> {
+    /// Wraps a `Vec`, which must not be empty
+    fn from_vec_unchecked(vec: Vec<T>) -> Self {
+        AtLeast::from_vec_unchecked(vec).into()
+    }
+
     /// Creates a new `NonemptyVec` with one element
     pub fn new(head: T) -> Self {
-        Self {
-            head,
-            body: Vec::new(),
-            _marker: PhantomData,
+        AtLeast::new([head]).into()
+    }
+
+    /// Creates a new `NonemptyVec` with one element
+    /// and space for at least `capacity` elements in total
+    pub fn with_capacity(head: T, capacity: usize) -> Self {
+        AtLeast::with_capacity([head], capacity).into()
+    }
+
+    /// Returns the number of elements `self` can hold without reallocating
+    pub fn capacity(&self) -> NonZeroUsize {
+        // Safety: the capacity is at least the length, which is at least 1
+        unsafe { NonZeroUsize::new_unchecked(self.inner.capacity()) }
+    }
+
+    /// Reserves space for at least `additional` more elements
+    pub fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional)
+    }
+
+    /// Shrinks the capacity as much as possible
+    pub fn shrink_to_fit(&mut self) {
+        self.inner.shrink_to_fit()
+    }
+
+    /// Creates a `NonemptyVec` from its head and tail;
+    /// this is the inverse of [`into_head_tail`](#method.into_head_tail)
+    ///
+    /// For `HeadFirst`, this moves the tail back by one element.
+    pub fn from_head_tail(head: T, mut tail: Vec<T>) -> Self {
+        if H::HEAD_FIRST {
+            tail.insert(0, head);
+        } else {
+            tail.push(head);
         }
+        Self::from_vec_unchecked(tail)
+    }
+
+    /// Collects an iterator into a `NonemptyVec`,
+    /// returning `None` if it is empty
+    pub fn from_iter_nonempty<I: IntoIterator<Item = T>>(iter: I) -> Option<Self> {
+        Self::try_from(iter.into_iter().collect::<Vec<_>>()).ok()
+    }
+
+    /// Returns the elements in logical order as a slice
+    pub fn as_slice(&self) -> &[T] {
+        self.inner.as_slice()
+    }
+
+    /// Like `as_slice` but mutable
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.inner.as_mut_slice()
     }
 
     /// The `head` is simply the element guaranteed to exist.
     /// It is not necessarily interpreted as the first item
     pub fn head(&self) -> &T {
-        &self.head
+        if H::HEAD_FIRST {
+            self.first()
+        } else {
+            self.last()
+        }
     }
 
     /// Like `head` but mutable
     pub fn head_mut(&mut self) -> &mut T {
-        &mut self.head
+        if H::HEAD_FIRST {
+            self.first_mut()
+        } else {
+            self.last_mut()
+        }
     }
 
     /// Consumes `self` and returns the head
     pub fn into_head(self) -> T {
-        self.head
+        if H::HEAD_FIRST {
+            self.into_first()
+        } else {
+            self.into_last()
+        }
     }
 
     /// The `tail` is simply all elements that are not guaranteed to exist
     pub fn tail(&self) -> &[T] {
-        &self.body
+        let slice = self.as_slice();
+        if H::HEAD_FIRST {
+            &slice[1..]
+        } else {
+            &slice[..slice.len() - 1]
+        }
     }
 
     /// Like `tail` but mutable
-    pub fn tail_mut(&mut self) -> &mut Vec<T> {
-        &mut self.body
+    pub fn tail_mut(&mut self) -> &mut [T] {
+        let slice = self.as_mut_slice();
+        let len = slice.len();
+        if H::HEAD_FIRST {
+            &mut slice[1..]
+        } else {
+            &mut slice[..len - 1]
+        }
     }
 
     /// Consumes `self` and returns the tail
     pub fn into_tail(self) -> Vec<T> {
-        self.body
+        self.into_head_tail().1
     }
 
     /// Consumes `self` and returns the head and the tail
+    ///
+    /// For `HeadFirst`, this moves the tail forward by one element.
     pub fn into_head_tail(self) -> (T, Vec<T>) {
-        (self.head, self.body)
+        if H::HEAD_FIRST {
+            self.split_first()
+        } else {
+            self.split_last()
+        }
     }
 
-    /// Returns the length of `self`
+    /// Consumes `self` and returns the first element and the rest, in logical order
     ///
-    /// # Edge Case
-    /// The length can be `usize::MAX + 1` - in that case, only `usize::MAX` is returned
-    ///
-    /// To circumvent this, you can work with `self.tail().len()`, which is always one less than `self.len()`
-    pub fn len(&self) -> NonZeroUsize {
-        // Saftey: as you can see, this is always at leats 1
-        unsafe { NonZeroUsize::new_unchecked(self.body.len().saturating_add(1)) }
+    /// This is `O(n)`, since the rest is moved forward by one element
+    pub fn split_first(self) -> (T, Vec<T>) {
+        let mut vec = self.into_vec();
+        let first = vec.remove(0);
+        (first, vec)
     }
 
-    // section: head-location specific functions
-    // the optimizer will optimize out the constant checks
+    /// Consumes `self` and returns the last element and the rest, in logical order
+    pub fn split_last(self) -> (T, Vec<T>) {
+        let mut vec = self.into_vec();
+        let last = vec.pop().unwrap();
+        (last, vec)
+    }
 
-    /// Pushes an element to the end of `self`
-    pub fn push(&mut self, t: T) {
+    /// Returns the contents of `self` as two slices, which together
+    /// contain all elements in logical order
+    ///
+    /// One of the two slices contains just the head.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let slice = self.as_slice();
         if H::HEAD_FIRST {
-            self.body.push(t);
+            slice.split_at(1)
         } else {
-            self.body.push(mem::replace(&mut self.head, t));
+            slice.split_at(slice.len() - 1)
+        }
+    }
+
+    /// Returns an iterator over references to the elements in logical order
+    pub fn iter(&self) -> iter::Iter<'_, T, H> {
+        iter::Iter {
+            iter: self.inner.iter(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over mutable references to the elements in logical order
+    pub fn iter_mut(&mut self) -> iter::IterMut<'_, T, H> {
+        iter::IterMut {
+            iter: self.inner.iter_mut(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Like `iter`, but returns a [`NonemptyIterator`](iter/trait.NonemptyIterator.html)
+    pub fn nonempty_iter(&self) -> iter::Nonempty<iter::Iter<'_, T, H>> {
+        iter::Nonempty { iter: self.iter() }
+    }
+
+    /// Like `iter_mut`, but returns a [`NonemptyIterator`](iter/trait.NonemptyIterator.html)
+    pub fn nonempty_iter_mut(&mut self) -> iter::Nonempty<iter::IterMut<'_, T, H>> {
+        iter::Nonempty {
+            iter: self.iter_mut(),
+        }
+    }
+
+    /// Like `into_iter`, but returns a [`NonemptyIterator`](iter/trait.NonemptyIterator.html)
+    pub fn into_nonempty_iter(self) -> iter::Nonempty<iter::IntoIter<T, H>> {
+        iter::Nonempty {
+            iter: self.into_iter(),
         }
     }
 
+    /// Returns the length of `self`
+    pub fn len(&self) -> NonZeroUsize {
+        // Safety: `AtLeast<T, 1>` is never empty
+        unsafe { NonZeroUsize::new_unchecked(self.inner.len()) }
+    }
+
+    /// Pushes an element to the end of `self`
+    pub fn push(&mut self, t: T) {
+        self.inner.push(t)
+    }
+
     /// Removes the last element from `self`
     /// (unless `self` has only one item left), returns it.
     pub fn pop(&mut self) -> Option<T> {
-        if H::HEAD_FIRST {
-            self.body.pop()
-        } else {
-            self.body
-                .pop()
-                .map(|new_head| mem::replace(&mut self.head, new_head))
-        }
+        self.inner.pop()
     }
 
     /// Inserts an element at the specified index
     pub fn insert(&mut self, index: usize, element: T) {
-        assert!(
-            index <= self.len().get(),
-            "insertion index (is {}) should be <= len (is {})",
-            index,
-            self.len()
-        );
-        if H::HEAD_FIRST {
-            if index == 0 {
-                let tmp = std::mem::replace(&mut self.head, element);
-                self.body.insert(0, tmp);
-            } else {
-                self.body.insert(index - 1, element);
-            }
-        } else if index == self.len().get() {
-            self.push(element);
-        } else {
-            self.body.insert(index, element);
-        }
+        self.inner.insert(index, element)
     }
 
     /// Removes an element from the specified index;
     /// Returns `None` if there is only one element left
     pub fn remove(&mut self, index: usize) -> Option<T> {
-        assert!(
-            index <= self.len().get(),
-            "removal index (is {}) should be <= len (is {})",
-            index,
-            self.len()
-        );
-        if self.len().get() == 1 {
-            return None;
-        }
-        Some(if H::HEAD_FIRST {
-            if index == 0 {
-                std::mem::replace(&mut self.head, self.body.remove(0))
-            } else {
-                self.body.remove(index - 1)
-            }
-        } else if index == self.len().get() {
-            self.pop()?
-        } else {
-            self.body.remove(index)
-        })
+        self.inner.remove(index)
+    }
+
+    /// Converts `self` to use `HeadFirst`, keeping the logical order
+    ///
+    /// This is free, since the elements are stored the same way for both head locations
+    pub fn into_head_first(self) -> NonemptyVec<T, HeadFirst> {
+        self.inner.into()
+    }
+
+    /// Converts `self` to use `HeadLast`, keeping the logical order
+    ///
+    /// This is free, since the elements are stored the same way for both head locations
+    pub fn into_head_last(self) -> NonemptyVec<T, HeadLast> {
+        self.inner.into()
+    }
+
+    /// Reverses the order of the elements
+    ///
+    /// The head stays the same element, which is why the `HeadLocation`
+    /// changes to the opposite one.
+    /// To keep the original `HeadLocation`, use
+    /// [`into_head_first`](#method.into_head_first) or
+    /// [`into_head_last`](#method.into_head_last) on the result.
+    pub fn reverse(mut self) -> NonemptyVec<T, H::Opposite> {
+        self.as_mut_slice().reverse();
+        self.inner.into()
     }
 
     /// Creates a `Vec` from all its elements
-    pub fn into_vec(mut self) -> Vec<T> {
-        let mut res;
-        if H::HEAD_FIRST {
-            res = vec![self.head];
-            res.append(&mut self.body);
-        } else {
-            res = self.body;
-            res.push(self.head);
-        }
-        res
+    pub fn into_vec(self) -> Vec<T> {
+        self.inner.into_vec()
     }
 
     /// The first element
     pub fn first(&self) -> &T {
-        if H::HEAD_FIRST {
-            &self.head
-        } else {
-            self.body.first().unwrap_or(&self.head)
-        }
+        self.inner.first()
     }
 
     /// The first element, mutable
     pub fn first_mut(&mut self) -> &mut T {
-        if H::HEAD_FIRST {
-            &mut self.head
-        } else {
-            self.body.first_mut().unwrap_or(&mut self.head)
-        }
+        self.inner.first_mut()
     }
 
     /// Returns the current first element, consuming `self`
-    pub fn into_first(mut self) -> T {
-        if H::HEAD_FIRST || self.body.is_empty() {
-            self.head
-        } else {
-            self.body.remove(0)
-        }
+    pub fn into_first(self) -> T {
+        self.into_vec().swap_remove(0)
     }
 
     /// The last element
     pub fn last(&self) -> &T {
-        if H::HEAD_FIRST {
-            self.body.last().unwrap_or(&self.head)
-        } else {
-            &self.head
-        }
+        self.inner.last()
     }
 
     /// The last element, mutable
     pub fn last_mut(&mut self) -> &mut T {
-        if H::HEAD_FIRST {
-            self.body.last_mut().unwrap_or(&mut self.head)
-        } else {
-            &mut self.head
-        }
+        self.inner.last_mut()
     }
 
     /// Returns the current last element, consuming `self`
-    pub fn into_last(mut self) -> T {
-        if H::HEAD_FIRST {
-            self.body.pop().unwrap_or(self.head)
-        } else {
-            self.head
+    pub fn into_last(self) -> T {
+        self.split_last().0
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if it is out of bounds
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.inner.get(index)
+    }
+
+    /// Like `get` but mutable
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.inner.get_mut(index)
+    }
+
+    /// Swaps the elements at indices `a` and `b`
+    ///
+    /// # Panics
+    /// Panics if `a` or `b` are out of bounds
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.as_mut_slice().swap(a, b)
+    }
+
+    /// Sorts `self`, preserving the order of equal elements
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.as_mut_slice().sort()
+    }
+
+    /// Sorts `self` with a key extraction function, preserving the order of equal elements
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, f: F) {
+        self.as_mut_slice().sort_by_key(f)
+    }
+
+    /// Sorts `self` with a comparator function, preserving the order of equal elements
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, compare: F) {
+        self.as_mut_slice().sort_by(compare)
+    }
+
+    /// Removes consecutive repeated elements
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b)
+    }
+
+    /// Removes consecutive elements that map to the same key
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    /// Removes consecutive elements that satisfy the given equality relation,
+    /// with the same semantics as `Vec::dedup_by`
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, same_bucket: F) {
+        // the first element is always kept, so this can't empty the `Vec`
+        self.inner.as_mut_vec().dedup_by(same_bucket)
+    }
+
+    /// Shortens `self` to `len` elements, dropping the rest
+    ///
+    /// Has no effect if `len` is greater than the current length
+    pub fn truncate(&mut self, len: NonZeroUsize) {
+        self.inner.truncate(len.get())
+    }
+
+    /// Splits off the elements starting at index `at`, returning them
+    ///
+    /// # Panics
+    /// Panics if `at > len`
+    pub fn split_off(&mut self, at: NonZeroUsize) -> Vec<T> {
+        self.inner.split_off(at.get())
+    }
+
+    /// Removes the elements in `range` and returns them as an iterator
+    ///
+    /// Returns `None` (without removing anything) if `range` covers all elements
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds or decreasing
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Option<Drain<'_, T>> {
+        let len = self.len().get();
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i + 1,
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => len,
+        };
+        assert!(
+            start <= end && end <= len,
+            "drain range (is {}..{}) should be in bounds (len is {})",
+            start,
+            end,
+            len
+        );
+        if start == 0 && end == len {
+            return None;
+        }
+        Some(self.inner.as_mut_vec().drain(start..end))
+    }
+
+    /// Retains only the elements for which `f` returns `true`,
+    /// visiting them in logical order
+    ///
+    /// If `f` returns `false` for every element, the last one is kept anyway,
+    /// since `self` cannot become empty.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let mut any = false;
+        let vec = self.inner.as_mut_vec();
+        let mut remaining = vec.len();
+        vec.retain(|t| {
+            remaining -= 1;
+            let keep = f(t);
+            any |= keep;
+            keep || (remaining == 0 && !any)
+        });
+    }
+}
+
+/// Creates a `NonemptyVec` with `T::default()` as its only element
+impl<T: Default, H: HeadLocation> Default for NonemptyVec<T, H> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T, H: HeadLocation> Index<usize> for NonemptyVec<T, H> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.inner[index]
+    }
+}
+
+impl<T, H: HeadLocation> IndexMut<usize> for NonemptyVec<T, H> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.inner[index]
+    }
+}
+
+impl<T, H: HeadLocation> AsRef<[T]> for NonemptyVec<T, H> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, H: HeadLocation> Borrow<[T]> for NonemptyVec<T, H> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, H: HeadLocation> TryFrom<Vec<T>> for NonemptyVec<T, H> {
+    /// The original `Vec` is returned if it is empty
+    type Error = Vec<T>;
+
+    fn try_from(vec: Vec<T>) -> Result<Self, Vec<T>> {
+        AtLeast::try_from(vec).map(Self::from)
+    }
+}
+
+impl<T, H: HeadLocation> From<AtLeast<T, 1>> for NonemptyVec<T, H> {
+    fn from(inner: AtLeast<T, 1>) -> Self {
+        Self {
+            inner,
+            _marker: PhantomData,
         }
     }
 }
 
-impl<T> IntoIterator for NonemptyVec<T> {
+impl<T, H: HeadLocation> From<NonemptyVec<T, H>> for AtLeast<T, 1> {
+    fn from(v: NonemptyVec<T, H>) -> Self {
+        v.inner
+    }
+}
+
+impl<T, H: HeadLocation> Extend<T> for NonemptyVec<T, H> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.inner.extend(iter)
+    }
+}
+
+impl<T, H: HeadLocation> IntoIterator for NonemptyVec<T, H> {
     type Item = T;
-    type IntoIter = std::vec::IntoIter<T>;
+    type IntoIter = iter::IntoIter<T, H>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        iter::IntoIter {
+            iter: self.inner.into_iter(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, H: HeadLocation> IntoIterator for &'a NonemptyVec<T, H> {
+    type Item = &'a T;
+    type IntoIter = iter::Iter<'a, T, H>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.into_vec().into_iter()
+        self.iter()
+    }
+}
+
+impl<'a, T, H: HeadLocation> IntoIterator for &'a mut NonemptyVec<T, H> {
+    type Item = &'a mut T;
+    type IntoIter = iter::IterMut<'a, T, H>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn macro_and_conversions() {
+        let v = nonempty_vec![1, 2, 3];
+        assert_eq!(v.clone().into_vec(), [1, 2, 3]);
+        assert_eq!(nonempty_vec![4].into_vec(), [4]);
+
+        assert_eq!(NonemptyVec::<i32>::try_from(vec![]), Err(vec![]));
+        let first = NonemptyVec::<_, HeadFirst>::try_from(vec![1, 2, 3]).unwrap();
+        let last = NonemptyVec::<_, HeadLast>::try_from(vec![1, 2, 3]).unwrap();
+        assert_eq!(first, v);
+        assert_eq!((*first.head(), *last.head()), (1, 3));
+        assert_eq!(last.clone().into_vec(), [1, 2, 3]);
+
+        assert_eq!(v.clone().split_first(), (1, vec![2, 3]));
+        assert_eq!(v.clone().split_last(), (3, vec![1, 2]));
+        assert_eq!(last.clone().split_first(), (1, vec![2, 3]));
+        assert_eq!(last.split_last(), (3, vec![1, 2]));
+
+        assert_eq!(NonemptyVec::<i32>::from_iter_nonempty(None), None);
+        let it = NonemptyVec::<_, HeadLast>::from_iter_nonempty(1..4).unwrap();
+        assert_eq!(it.into_vec(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn ordering_and_capacity() {
+        let a = NonemptyVec::<_, HeadLast>::try_from(vec![1, 5]).unwrap();
+        let b = NonemptyVec::<_, HeadLast>::try_from(vec![2, 0]).unwrap();
+        assert!(a < b);
+        assert_eq!(a.cmp(&a), Ordering::Equal);
+        assert_eq!(a.as_slices(), (&[1][..], &[5][..]));
+
+        let mut v = NonemptyVec::<_, HeadFirst>::with_capacity(0, 10);
+        assert!(v.capacity().get() >= 10);
+        v.reserve(20);
+        assert!(v.capacity().get() >= 21);
+        v.extend(1..5);
+        v.shrink_to_fit();
+        let mut model = vec![0];
+        model.reserve(20);
+        model.extend(1..5);
+        model.shrink_to_fit();
+        assert_eq!(v.capacity().get(), model.capacity());
+        assert_eq!(v.capacity(), v.len());
+    }
+
+    #[test]
+    fn slice_view() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashSet;
+        use std::hash::Hasher;
+
+        fn hash<T: Hash + ?Sized>(t: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            t.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let last = NonemptyVec::<_, HeadLast>::try_from(vec![1, 2, 3]).unwrap();
+        let slice: &[i32] = last.as_ref();
+        assert_eq!(slice, [1, 2, 3]);
+        assert_eq!(hash(&last), hash(slice));
+        assert_eq!(last.as_slice(), nonempty_vec![1, 2, 3].as_slice());
+
+        let set: HashSet<_> = vec![nonempty_vec![1, 2], nonempty_vec![3]]
+            .into_iter()
+            .collect();
+        assert!(set.contains(&[1, 2][..]));
+        assert!(!set.contains(&[2][..]));
+    }
+
+    #[test]
+    fn head_location() {
+        let v = nonempty_vec![1, 2, 3];
+        let last = v.clone().into_head_last();
+        assert_eq!(*last.head(), 3);
+        assert_eq!(last.clone().into_vec(), [1, 2, 3]);
+        assert_eq!(last.clone().into_head_first(), v);
+        assert_eq!(last.clone().into_head_last(), last);
+        assert_eq!(nonempty_vec![1].into_head_last().into_vec(), [1]);
+
+        let rev = v.clone().reverse();
+        assert_eq!(*rev.head(), 1);
+        assert_eq!(rev.clone().into_vec(), [3, 2, 1]);
+        assert_eq!(rev.reverse(), v);
+        assert_eq!(last.reverse().into_vec(), [3, 2, 1]);
+    }
+
+    #[test]
+    fn extend() {
+        let mut first = nonempty_vec![1];
+        let mut last = NonemptyVec::<_, HeadLast>::from_head_tail(1, vec![]);
+        first.extend(vec![2, 3]);
+        last.extend(vec![2, 3]);
+        assert_eq!(first.into_vec(), [1, 2, 3]);
+        assert_eq!(last.clone().into_vec(), [1, 2, 3]);
+    }
+
+    /// A tiny xorshift generator, so the model tests are reproducible
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, n: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % n as u64) as usize
+        }
+    }
+
+    fn contents<H: HeadLocation>(v: &NonemptyVec<(usize, usize), H>) -> Vec<(usize, usize)> {
+        v.iter().copied().collect()
+    }
+
+    /// Applies random operations to both a `NonemptyVec` and a `Vec`, comparing them after each step
+    fn model_test<H: HeadLocation>(seed: u64) {
+        let mut rng = Rng(seed);
+        let mut id = 0;
+        let mut next = |rng: &mut Rng| {
+            id += 1;
+            (rng.below(4), id)
+        };
+        let first = next(&mut rng);
+        let mut v = NonemptyVec::<_, H>::new(first);
+        let mut model = vec![first];
+
+        for _ in 0..2000 {
+            let len = model.len();
+            match rng.below(24) {
+                0 | 1 | 14..=16 => {
+                    let t = next(&mut rng);
+                    v.push(t);
+                    model.push(t);
+                }
+                2 => {
+                    let expected = if len == 1 { None } else { model.pop() };
+                    assert_eq!(v.pop(), expected);
+                }
+                3 => {
+                    let (i, t) = (rng.below(len + 1), next(&mut rng));
+                    v.insert(i, t);
+                    model.insert(i, t);
+                }
+                4 => {
+                    let i = rng.below(len);
+                    let expected = if len == 1 {
+                        None
+                    } else {
+                        Some(model.remove(i))
+                    };
+                    assert_eq!(v.remove(i), expected);
+                }
+                5 => {
+                    let i = rng.below(len + 1);
+                    assert_eq!(v.get(i), model.get(i));
+                    if i < len {
+                        let t = next(&mut rng);
+                        v[i] = t;
+                        model[i] = t;
+                    }
+                }
+                6 => {
+                    let (a, b) = (rng.below(len), rng.below(len));
+                    v.swap(a, b);
+                    model.swap(a, b);
+                }
+                7 => {
+                    v.sort_by_key(|t| t.0);
+                    model.sort_by_key(|t| t.0);
+                }
+                8 => {
+                    v.dedup_by_key(|t| t.0);
+                    model.dedup_by_key(|t| t.0);
+                }
+                9 => {
+                    let n = rng.below(len + 2).max(1);
+                    v.truncate(NonZeroUsize::new(n).unwrap());
+                    model.truncate(n);
+                }
+                10 => {
+                    let at = rng.below(len) + 1;
+                    assert_eq!(
+                        v.split_off(NonZeroUsize::new(at).unwrap()),
+                        model.split_off(at)
+                    );
+                }
+                11 => {
+                    let start = rng.below(len + 1);
+                    let end = start + rng.below(len + 1 - start);
+                    match v.drain(start..end) {
+                        Some(drain) => {
+                            assert_eq!(
+                                drain.collect::<Vec<_>>(),
+                                model.drain(start..end).collect::<Vec<_>>()
+                            )
+                        }
+                        None => assert_eq!((start, end), (0, len)),
+                    }
+                }
+                12 => {
+                    let k = rng.below(4);
+                    v.retain(|t| t.0 != k);
+                    let last = model[len - 1];
+                    model.retain(|t| t.0 != k);
+                    if model.is_empty() {
+                        model.push(last);
+                    }
+                }
+                _ => {
+                    let t = next(&mut rng);
+                    v.extend(Some(t));
+                    model.push(t);
+                }
+            }
+            assert_eq!(contents(&v), model);
+            assert_eq!(v.len().get(), model.len());
+            assert_eq!((v.first(), v.last()), (&model[0], &model[model.len() - 1]));
+        }
+    }
+
+    #[test]
+    fn model_head_first() {
+        for seed in 1..20 {
+            model_test::<HeadFirst>(seed);
+        }
+    }
+
+    #[test]
+    fn model_head_last() {
+        for seed in 1..20 {
+            model_test::<HeadLast>(seed);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_out_of_bounds() {
+        let mut v = nonempty_vec![1, 2];
+        v.remove(2);
     }
 }
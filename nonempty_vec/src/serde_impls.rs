@@ -0,0 +1,69 @@
+//! `Serialize` and `Deserialize` impls for `NonemptyVec`, enabled by the `serde` feature
+
+use crate::{HeadLocation, NonemptyVec};
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+/// Serializes as a plain sequence in logical order
+impl<T: Serialize, H: HeadLocation> Serialize for NonemptyVec<T, H> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len().get()))?;
+        for t in self {
+            seq.serialize_element(t)?;
+        }
+        seq.end()
+    }
+}
+
+struct NonemptyVecVisitor<T, H>(PhantomData<(T, H)>);
+
+impl<'de, T: Deserialize<'de>, H: HeadLocation> Visitor<'de> for NonemptyVecVisitor<T, H> {
+    type Value = NonemptyVec<T, H>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a nonempty sequence")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let head = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let mut res = NonemptyVec::new(head);
+        while let Some(t) = seq.next_element()? {
+            res.push(t);
+        }
+        Ok(res)
+    }
+}
+
+/// Deserializes from a sequence, failing if it is empty
+impl<'de, T: Deserialize<'de>, H: HeadLocation> Deserialize<'de> for NonemptyVec<T, H> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(NonemptyVecVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{nonempty_vec, HeadLast, NonemptyVec};
+
+    #[test]
+    fn roundtrip() {
+        let v = nonempty_vec![1, 2, 3];
+        assert_eq!(serde_json::to_string(&v).unwrap(), "[1,2,3]");
+        let last: NonemptyVec<i32, HeadLast> = serde_json::from_str("[1,2,3]").unwrap();
+        assert_eq!(*last.head(), 3);
+        assert_eq!(serde_json::to_string(&last).unwrap(), "[1,2,3]");
+    }
+
+    #[test]
+    fn reject_empty() {
+        let err = serde_json::from_str::<NonemptyVec<i32>>("[]").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid length 0, expected a nonempty sequence at line 1 column 2"
+        );
+    }
+}
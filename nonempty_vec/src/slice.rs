@@ -0,0 +1,171 @@
+//! The [`NonemptySlice`](struct.NonemptySlice.html) view
+
+use crate::iter::Nonempty;
+use core::borrow::Borrow;
+use core::num::NonZeroUsize;
+use core::ops::{Deref, DerefMut};
+use core::slice;
+
+/// A slice that always has at least one element
+///
+/// This is an unsized type, so it is always used behind a reference,
+/// just like `[T]` itself.
+#[repr(transparent)]
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct NonemptySlice<T>([T]);
+
+#[allow(clippy::len_without_is_empty)]
+impl<T> NonemptySlice<T> {
+    /// Views `slice` as a `NonemptySlice`, returning `None` if it is empty
+    pub fn new(slice: &[T]) -> Option<&Self> {
+        if slice.is_empty() {
+            None
+        } else {
+            // Safety: `NonemptySlice<T>` is `repr(transparent)` over `[T]`
+            Some(unsafe { &*(slice as *const [T] as *const Self) })
+        }
+    }
+
+    /// Like `new` but mutable
+    pub fn new_mut(slice: &mut [T]) -> Option<&mut Self> {
+        if slice.is_empty() {
+            None
+        } else {
+            // Safety: `NonemptySlice<T>` is `repr(transparent)` over `[T]`
+            Some(unsafe { &mut *(slice as *mut [T] as *mut Self) })
+        }
+    }
+
+    /// Views a single element as a `NonemptySlice`
+    pub fn from_ref(t: &T) -> &Self {
+        Self::new(slice::from_ref(t)).unwrap()
+    }
+
+    /// Returns the underlying slice
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    /// Like `as_slice` but mutable
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+
+    /// Returns the length of `self`
+    pub fn len(&self) -> NonZeroUsize {
+        NonZeroUsize::new(self.0.len()).unwrap()
+    }
+
+    /// The first element
+    pub fn first(&self) -> &T {
+        &self.0[0]
+    }
+
+    /// The first element, mutable
+    pub fn first_mut(&mut self) -> &mut T {
+        &mut self.0[0]
+    }
+
+    /// The last element
+    pub fn last(&self) -> &T {
+        &self.0[self.0.len() - 1]
+    }
+
+    /// The last element, mutable
+    pub fn last_mut(&mut self) -> &mut T {
+        let last = self.0.len() - 1;
+        &mut self.0[last]
+    }
+
+    /// Returns the first element and the rest
+    pub fn split_first(&self) -> (&T, &[T]) {
+        self.0.split_first().unwrap()
+    }
+
+    /// Returns the last element and the rest
+    pub fn split_last(&self) -> (&T, &[T]) {
+        self.0.split_last().unwrap()
+    }
+
+    /// Like `iter`, but returns a [`NonemptyIterator`](iter/trait.NonemptyIterator.html)
+    pub fn nonempty_iter(&self) -> Nonempty<slice::Iter<'_, T>> {
+        Nonempty {
+            iter: self.0.iter(),
+        }
+    }
+
+    /// Like `iter_mut`, but returns a [`NonemptyIterator`](iter/trait.NonemptyIterator.html)
+    pub fn nonempty_iter_mut(&mut self) -> Nonempty<slice::IterMut<'_, T>> {
+        Nonempty {
+            iter: self.0.iter_mut(),
+        }
+    }
+}
+
+impl<T> Deref for NonemptySlice<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for NonemptySlice<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+}
+
+impl<T> AsRef<[T]> for NonemptySlice<T> {
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> Borrow<[T]> for NonemptySlice<T> {
+    fn borrow(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<'a, T> IntoIterator for &'a NonemptySlice<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut NonemptySlice<T> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NonemptySlice;
+    use crate::NonemptyIterator;
+
+    #[test]
+    fn view() {
+        assert!(NonemptySlice::<i32>::new(&[]).is_none());
+        let s = NonemptySlice::new(&[3, 1, 2]).unwrap();
+        assert_eq!((*s.first(), *s.last()), (3, 2));
+        assert_eq!(*s.nonempty_iter().max(), 3);
+        assert_eq!(*s.nonempty_iter().min(), 1);
+        assert_eq!(s.iter().next_back(), Some(&2));
+        assert_eq!(s.split_first(), (&3, &[1, 2][..]));
+        assert_eq!(s.len().get(), 3);
+
+        let mut arr = [1, 2];
+        let m = NonemptySlice::new_mut(&mut arr).unwrap();
+        *m.last_mut() = 5;
+        m.sort_by(|a, b| b.cmp(a));
+        assert_eq!(arr, [5, 1]);
+    }
+}
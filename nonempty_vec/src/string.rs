@@ -0,0 +1,156 @@
+//! The [`NonemptyString`](struct.NonemptyString.html) type
+
+use crate::iter::{FromNonemptyIterator, NonemptyIterator};
+use alloc::string::{String, ToString};
+use core::borrow::Borrow;
+use core::convert::TryFrom;
+use core::fmt;
+use core::num::NonZeroUsize;
+use core::ops::Deref;
+
+/// A `String` that always has at least one character
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct NonemptyString(String);
+
+#[allow(clippy::len_without_is_empty)]
+impl NonemptyString {
+    /// Creates a new `NonemptyString` with one character
+    pub fn new(c: char) -> Self {
+        Self(c.to_string())
+    }
+
+    /// Returns the underlying string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consumes `self` and returns the underlying `String`
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// Returns the length of `self` in bytes
+    pub fn len(&self) -> NonZeroUsize {
+        NonZeroUsize::new(self.0.len()).unwrap()
+    }
+
+    /// The first character
+    pub fn first(&self) -> char {
+        self.0.chars().next().unwrap()
+    }
+
+    /// The last character
+    pub fn last(&self) -> char {
+        self.0.chars().next_back().unwrap()
+    }
+
+    /// Appends a character to the end of `self`
+    pub fn push(&mut self, c: char) {
+        self.0.push(c)
+    }
+
+    /// Appends a string slice to the end of `self`
+    pub fn push_str(&mut self, s: &str) {
+        self.0.push_str(s)
+    }
+
+    /// Removes the last character from `self`
+    /// (unless `self` has only one character left), returns it.
+    pub fn pop(&mut self) -> Option<char> {
+        let mut chars = self.0.chars();
+        chars.next_back();
+        if chars.as_str().is_empty() {
+            None
+        } else {
+            self.0.pop()
+        }
+    }
+}
+
+impl Deref for NonemptyString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for NonemptyString {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for NonemptyString {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for NonemptyString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl TryFrom<String> for NonemptyString {
+    /// The original `String` is returned if it is empty
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, String> {
+        if s.is_empty() {
+            Err(s)
+        } else {
+            Ok(Self(s))
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for NonemptyString {
+    type Error = &'a str;
+
+    fn try_from(s: &'a str) -> Result<Self, &'a str> {
+        if s.is_empty() {
+            Err(s)
+        } else {
+            Ok(Self(s.to_string()))
+        }
+    }
+}
+
+impl From<NonemptyString> for String {
+    fn from(s: NonemptyString) -> String {
+        s.0
+    }
+}
+
+impl Extend<char> for NonemptyString {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+impl FromNonemptyIterator<char> for NonemptyString {
+    fn from_nonempty_iter<I: NonemptyIterator<Item = char>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NonemptyString;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn string() {
+        assert_eq!(NonemptyString::try_from(""), Err(""));
+        let mut s = NonemptyString::try_from("añb").unwrap();
+        assert_eq!((s.first(), s.last()), ('a', 'b'));
+        assert_eq!(s.pop(), Some('b'));
+        assert_eq!(s.pop(), Some('ñ'));
+        assert_eq!(s.pop(), None);
+        s.push_str("bc");
+        assert_eq!(s.to_string(), "abc");
+        assert_eq!(String::from(s), "abc");
+    }
+}
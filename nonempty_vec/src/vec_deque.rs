@@ -0,0 +1,383 @@
+//! The [`NonemptyVecDeque`](struct.NonemptyVecDeque.html) type and its iterators
+
+use crate::iter::{FromNonemptyIterator, Nonempty, NonemptyIterator};
+use crate::{HeadFirst, HeadLast, HeadLocation, NonemptyVec};
+use alloc::collections::{vec_deque, VecDeque};
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+use core::mem;
+use core::num::NonZeroUsize;
+
+/// A `VecDeque` that always has at least one element
+///
+/// Unlike with [`NonemptyVec`](../struct.NonemptyVec.html), the guaranteed element
+/// is stored separately, at the end the `HeadLocation` decides on.
+/// Since both ends of a `VecDeque` are cheap to modify, all operations
+/// on the ends are `O(1)` regardless of that choice.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct NonemptyVecDeque<T, H: HeadLocation = HeadFirst> {
+    head: T,
+    body: VecDeque<T>,
+    _marker: PhantomData<H>,
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl<T, H: HeadLocation> NonemptyVecDeque<T, H> {
+    /// Creates a new `NonemptyVecDeque` with one element
+    pub fn new(head: T) -> Self {
+        Self {
+            head,
+            body: VecDeque::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The `head` is simply the element guaranteed to exist.
+    /// It is not necessarily interpreted as the first item
+    pub fn head(&self) -> &T {
+        &self.head
+    }
+
+    /// The `tail` is simply all elements that are not guaranteed to exist
+    pub fn tail(&self) -> &VecDeque<T> {
+        &self.body
+    }
+
+    /// Returns the length of `self`
+    pub fn len(&self) -> NonZeroUsize {
+        NonZeroUsize::new(self.body.len() + 1).unwrap()
+    }
+
+    /// The first element
+    pub fn front(&self) -> &T {
+        if H::HEAD_FIRST {
+            &self.head
+        } else {
+            self.body.front().unwrap_or(&self.head)
+        }
+    }
+
+    /// The first element, mutable
+    pub fn front_mut(&mut self) -> &mut T {
+        if H::HEAD_FIRST {
+            &mut self.head
+        } else {
+            self.body.front_mut().unwrap_or(&mut self.head)
+        }
+    }
+
+    /// The last element
+    pub fn back(&self) -> &T {
+        if H::HEAD_FIRST {
+            self.body.back().unwrap_or(&self.head)
+        } else {
+            &self.head
+        }
+    }
+
+    /// The last element, mutable
+    pub fn back_mut(&mut self) -> &mut T {
+        if H::HEAD_FIRST {
+            self.body.back_mut().unwrap_or(&mut self.head)
+        } else {
+            &mut self.head
+        }
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if it is out of bounds
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if H::HEAD_FIRST {
+            match index {
+                0 => Some(&self.head),
+                _ => self.body.get(index - 1),
+            }
+        } else if index == self.body.len() {
+            Some(&self.head)
+        } else {
+            self.body.get(index)
+        }
+    }
+
+    /// Adds an element to the front of `self`
+    pub fn push_front(&mut self, t: T) {
+        if H::HEAD_FIRST {
+            self.body.push_front(mem::replace(&mut self.head, t));
+        } else {
+            self.body.push_front(t);
+        }
+    }
+
+    /// Adds an element to the back of `self`
+    pub fn push_back(&mut self, t: T) {
+        if H::HEAD_FIRST {
+            self.body.push_back(t);
+        } else {
+            self.body.push_back(mem::replace(&mut self.head, t));
+        }
+    }
+
+    /// Removes the first element from `self`
+    /// (unless `self` has only one item left), returns it.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if H::HEAD_FIRST {
+            self.body
+                .pop_front()
+                .map(|new_head| mem::replace(&mut self.head, new_head))
+        } else {
+            self.body.pop_front()
+        }
+    }
+
+    /// Removes the last element from `self`
+    /// (unless `self` has only one item left), returns it.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if H::HEAD_FIRST {
+            self.body.pop_back()
+        } else {
+            self.body
+                .pop_back()
+                .map(|new_head| mem::replace(&mut self.head, new_head))
+        }
+    }
+
+    /// Returns an iterator over references to the elements in logical order
+    pub fn iter(&self) -> Iter<'_, T, H> {
+        Iter {
+            head: Some(&self.head),
+            body: self.body.iter(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over mutable references to the elements in logical order
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, H> {
+        IterMut {
+            head: Some(&mut self.head),
+            body: self.body.iter_mut(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Like `iter`, but returns a [`NonemptyIterator`](iter/trait.NonemptyIterator.html)
+    pub fn nonempty_iter(&self) -> Nonempty<Iter<'_, T, H>> {
+        Nonempty { iter: self.iter() }
+    }
+
+    /// Like `iter_mut`, but returns a [`NonemptyIterator`](iter/trait.NonemptyIterator.html)
+    pub fn nonempty_iter_mut(&mut self) -> Nonempty<IterMut<'_, T, H>> {
+        Nonempty {
+            iter: self.iter_mut(),
+        }
+    }
+
+    /// Like `into_iter`, but returns a [`NonemptyIterator`](iter/trait.NonemptyIterator.html)
+    pub fn into_nonempty_iter(self) -> Nonempty<IntoIter<T, H>> {
+        Nonempty {
+            iter: self.into_iter(),
+        }
+    }
+
+    /// Converts `self` to use `HeadFirst`, keeping the logical order
+    ///
+    /// This is always `O(1)`
+    pub fn into_head_first(self) -> NonemptyVecDeque<T, HeadFirst> {
+        self.into_head_location()
+    }
+
+    /// Converts `self` to use `HeadLast`, keeping the logical order
+    ///
+    /// This is always `O(1)`
+    pub fn into_head_last(self) -> NonemptyVecDeque<T, HeadLast> {
+        self.into_head_location()
+    }
+
+    fn into_head_location<H2: HeadLocation>(self) -> NonemptyVecDeque<T, H2> {
+        let Self {
+            mut head, mut body, ..
+        } = self;
+        if H::HEAD_FIRST != H2::HEAD_FIRST {
+            if H::HEAD_FIRST {
+                if let Some(last) = body.pop_back() {
+                    body.push_front(mem::replace(&mut head, last));
+                }
+            } else if let Some(first) = body.pop_front() {
+                body.push_back(mem::replace(&mut head, first));
+            }
+        }
+        NonemptyVecDeque {
+            head,
+            body,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Reverses the order of the elements
+    ///
+    /// Just like [`NonemptyVec::reverse`](../struct.NonemptyVec.html#method.reverse),
+    /// this only reverses the tail and changes the `HeadLocation` to the opposite one.
+    pub fn reverse(mut self) -> NonemptyVecDeque<T, H::Opposite> {
+        self.body.make_contiguous().reverse();
+        NonemptyVecDeque {
+            head: self.head,
+            body: self.body,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a `VecDeque` from all its elements
+    pub fn into_vec_deque(mut self) -> VecDeque<T> {
+        if H::HEAD_FIRST {
+            self.body.push_front(self.head);
+        } else {
+            self.body.push_back(self.head);
+        }
+        self.body
+    }
+}
+
+head_body_iter! {
+    /// An iterator over references to the elements of a `NonemptyVecDeque`
+    Iter<'a, T>: &'a T, vec_deque::Iter<'a, T> => &'a T
+}
+
+head_body_iter! {
+    /// An iterator over mutable references to the elements of a `NonemptyVecDeque`
+    IterMut<'a, T>: &'a mut T, vec_deque::IterMut<'a, T> => &'a mut T
+}
+
+head_body_iter! {
+    /// An iterator that moves the elements out of a `NonemptyVecDeque`
+    IntoIter<T>: T, vec_deque::IntoIter<T> => T
+}
+
+impl<T, H: HeadLocation> IntoIterator for NonemptyVecDeque<T, H> {
+    type Item = T;
+    type IntoIter = IntoIter<T, H>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            head: Some(self.head),
+            body: self.body.into_iter(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, H: HeadLocation> IntoIterator for &'a NonemptyVecDeque<T, H> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, H>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, H: HeadLocation> IntoIterator for &'a mut NonemptyVecDeque<T, H> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T, H>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, H: HeadLocation> Extend<T> for NonemptyVecDeque<T, H> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|t| self.push_back(t));
+    }
+}
+
+impl<T, H: HeadLocation> FromNonemptyIterator<T> for NonemptyVecDeque<T, H> {
+    fn from_nonempty_iter<I: NonemptyIterator<Item = T>>(iter: I) -> Self {
+        let (first, rest) = iter.split_first();
+        let mut res = Self::new(first);
+        res.extend(rest);
+        res
+    }
+}
+
+impl<T, H: HeadLocation> TryFrom<VecDeque<T>> for NonemptyVecDeque<T, H> {
+    /// The original `VecDeque` is returned if it is empty
+    type Error = VecDeque<T>;
+
+    fn try_from(mut deque: VecDeque<T>) -> Result<Self, VecDeque<T>> {
+        let head = if H::HEAD_FIRST {
+            deque.pop_front()
+        } else {
+            deque.pop_back()
+        };
+        match head {
+            Some(head) => Ok(Self {
+                head,
+                body: deque,
+                _marker: PhantomData,
+            }),
+            None => Err(deque),
+        }
+    }
+}
+
+impl<T, H: HeadLocation> From<NonemptyVec<T, H>> for NonemptyVecDeque<T, H> {
+    fn from(v: NonemptyVec<T, H>) -> Self {
+        let mut body = VecDeque::from(v.into_vec());
+        let head = if H::HEAD_FIRST {
+            body.pop_front()
+        } else {
+            body.pop_back()
+        };
+        Self {
+            head: head.unwrap(),
+            body,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, H: HeadLocation> From<NonemptyVecDeque<T, H>> for NonemptyVec<T, H> {
+    fn from(v: NonemptyVecDeque<T, H>) -> Self {
+        NonemptyVec::from_vec_unchecked(v.into_vec_deque().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NonemptyVecDeque;
+    use crate::{HeadFirst, HeadLast, HeadLocation, NonemptyIterator, NonemptyVec};
+    use std::collections::VecDeque;
+    use std::convert::TryFrom;
+
+    fn both_ends<H: HeadLocation>() {
+        let mut d = NonemptyVecDeque::<_, H>::new(2);
+        d.push_front(1);
+        d.push_back(3);
+        assert_eq!((*d.front(), *d.back()), (1, 3));
+        assert_eq!(
+            d.nonempty_iter().collect::<NonemptyVec<_>>().into_vec(),
+            [&1, &2, &3]
+        );
+        assert_eq!(d.pop_front(), Some(1));
+        assert_eq!(d.pop_back(), Some(3));
+        assert_eq!(d.pop_back(), None);
+        assert_eq!(d.pop_front(), None);
+        assert_eq!(*d.front(), 2);
+    }
+
+    #[test]
+    fn deque() {
+        both_ends::<HeadFirst>();
+        both_ends::<HeadLast>();
+
+        let deque: VecDeque<_> = vec![1, 2, 3].into();
+        let d = NonemptyVecDeque::<_, HeadLast>::try_from(deque.clone()).unwrap();
+        assert_eq!(d.get(2), Some(&3));
+        assert_eq!(d.into_vec_deque(), deque);
+        let v: NonemptyVec<_, HeadLast> = NonemptyVecDeque::try_from(deque.clone()).unwrap().into();
+        assert_eq!(v.into_vec(), [1, 2, 3]);
+
+        let d = NonemptyVecDeque::<_, HeadFirst>::try_from(deque).unwrap();
+        let last = d.clone().into_head_last();
+        assert_eq!(*last.head(), 3);
+        assert_eq!(last.clone().into_head_first(), d);
+        assert_eq!(last.reverse().into_vec_deque(), [3, 2, 1]);
+    }
+}
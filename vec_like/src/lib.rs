@@ -40,23 +40,104 @@ pub trait PopBack<T> {
     fn pop_back(&mut self) -> Option<T>;
 }
 
+/// Add several elements to the front of the list
+pub trait ExtendFront<T>: PushFront<T> {
+    /// Try to add the elements of `iter` to the front of the list,
+    /// keeping their order, so that the first one ends up at the very front.
+    ///
+    /// Fails like [`push_front`](trait.PushFront.html#tymethod.push_front)
+    /// with the error for the first element that couldn't be added.
+    /// The elements before it stay in the list, the ones after it are dropped.
+    fn extend_front<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), Self::Err>;
+}
+
+/// Add several elements to the back of the list
+pub trait ExtendBack<T>: PushBack<T> {
+    /// Try to add the elements of `iter` to the back of the list, keeping their order.
+    ///
+    /// Fails like [`push_back`](trait.PushBack.html#tymethod.push_back)
+    /// with the error for the first element that couldn't be added.
+    /// The elements before it stay in the list, the ones after it are dropped.
+    fn extend_back<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), Self::Err>;
+}
+
+/// Remove several elements from the front of the list
+pub trait DrainFront<T> {
+    /// The iterator over the removed elements
+    type Drain<'a>: Iterator<Item = T>
+    where
+        Self: 'a;
+
+    /// Remove up to `n` elements from the front of the list and iterate over them in order.
+    ///
+    /// Fewer elements are removed if the list is shorter
+    /// (or can't become empty, like `NonemptyVec`).
+    fn drain_front(&mut self, n: usize) -> Self::Drain<'_>;
+}
+
+/// Remove several elements from the back of the list
+pub trait DrainBack<T> {
+    /// The iterator over the removed elements
+    type Drain<'a>: Iterator<Item = T>
+    where
+        Self: 'a;
+
+    /// Remove up to `n` elements from the back of the list and iterate over them in order.
+    ///
+    /// Fewer elements are removed if the list is shorter
+    /// (or can't become empty, like `NonemptyVec`).
+    fn drain_back(&mut self, n: usize) -> Self::Drain<'_>;
+}
+
 /// Get the element at the front of the list
 pub trait PeekFront<T: ?Sized> {
     /// Try to get the element at the front of the list.
-    fn peek_front(&mut self) -> Option<&T>;
+    fn peek_front(&self) -> Option<&T>;
 }
 
 /// Get the element at the back of the list
 pub trait PeekBack<T: ?Sized> {
     /// Try to get the element at the back of the list.
-    fn peek_back(&mut self) -> Option<&T>;
+    fn peek_back(&self) -> Option<&T>;
+}
+
+/// Get mutable access to the element at the front of the list
+pub trait PeekFrontMut<T: ?Sized> {
+    /// Try to get the element at the front of the list.
+    fn peek_front_mut(&mut self) -> Option<&mut T>;
+}
+
+/// Get mutable access to the element at the back of the list
+pub trait PeekBackMut<T: ?Sized> {
+    /// Try to get the element at the back of the list.
+    fn peek_back_mut(&mut self) -> Option<&mut T>;
+}
+
+/// Get the element at the front of a lazy source,
+/// which may have to produce (pull) it first
+///
+/// Storage types that implement [`PeekFront`](trait.PeekFront.html) implement this as well.
+pub trait PullPeekFront<T: ?Sized> {
+    /// Try to get the element at the front, producing it if necessary.
+    fn pull_peek_front(&mut self) -> Option<&T>;
+}
+
+/// Get the element at the back of a lazy source,
+/// which may have to produce (pull) it first
+///
+/// Storage types that implement [`PeekBack`](trait.PeekBack.html) implement this as well.
+pub trait PullPeekBack<T: ?Sized> {
+    /// Try to get the element at the back, producing it if necessary.
+    fn pull_peek_back(&mut self) -> Option<&T>;
 }
 
 #[cfg(feature = "use-std")]
 mod std_impls {
     use super::*;
     use nonempty_vec::NonemptyVec;
-    use std::collections::{LinkedList, VecDeque};
+    use std::collections::{linked_list, vec_deque, LinkedList, VecDeque};
+    use std::convert::TryFrom;
+    use std::{mem, vec};
 
     // section: impl Vec
 
@@ -95,17 +176,77 @@ mod std_impls {
     }
 
     impl<T> PeekBack<T> for Vec<T> {
-        fn peek_back(&mut self) -> Option<&T> {
+        fn peek_back(&self) -> Option<&T> {
             self.last()
         }
     }
 
     impl<T> PeekFront<T> for Vec<T> {
-        fn peek_front(&mut self) -> Option<&T> {
+        fn peek_front(&self) -> Option<&T> {
             self.first()
         }
     }
 
+    impl<T> PeekFrontMut<T> for Vec<T> {
+        fn peek_front_mut(&mut self) -> Option<&mut T> {
+            self.first_mut()
+        }
+    }
+
+    impl<T> PeekBackMut<T> for Vec<T> {
+        fn peek_back_mut(&mut self) -> Option<&mut T> {
+            self.last_mut()
+        }
+    }
+
+    impl<T> PullPeekFront<T> for Vec<T> {
+        fn pull_peek_front(&mut self) -> Option<&T> {
+            self.peek_front()
+        }
+    }
+
+    impl<T> PullPeekBack<T> for Vec<T> {
+        fn pull_peek_back(&mut self) -> Option<&T> {
+            self.peek_back()
+        }
+    }
+
+    impl<T> ExtendFront<T> for Vec<T> {
+        fn extend_front<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), Self::Err> {
+            self.splice(0..0, iter);
+            Ok(())
+        }
+    }
+
+    impl<T> ExtendBack<T> for Vec<T> {
+        fn extend_back<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), Self::Err> {
+            self.extend(iter);
+            Ok(())
+        }
+    }
+
+    impl<T> DrainFront<T> for Vec<T> {
+        type Drain<'a>
+            = vec::Drain<'a, T>
+        where
+            T: 'a;
+
+        fn drain_front(&mut self, n: usize) -> Self::Drain<'_> {
+            self.drain(..n.min(self.len()))
+        }
+    }
+
+    impl<T> DrainBack<T> for Vec<T> {
+        type Drain<'a>
+            = vec::Drain<'a, T>
+        where
+            T: 'a;
+
+        fn drain_back(&mut self, n: usize) -> Self::Drain<'_> {
+            self.drain(self.len().saturating_sub(n)..)
+        }
+    }
+
     // section: impl NonemptyVec
 
     impl<T> PushFront<T> for NonemptyVec<T> {
@@ -139,17 +280,84 @@ mod std_impls {
     }
 
     impl<T> PeekBack<T> for NonemptyVec<T> {
-        fn peek_back(&mut self) -> Option<&T> {
+        fn peek_back(&self) -> Option<&T> {
             Some(self.last())
         }
     }
 
     impl<T> PeekFront<T> for NonemptyVec<T> {
-        fn peek_front(&mut self) -> Option<&T> {
+        fn peek_front(&self) -> Option<&T> {
             Some(self.first())
         }
     }
 
+    impl<T> PeekFrontMut<T> for NonemptyVec<T> {
+        fn peek_front_mut(&mut self) -> Option<&mut T> {
+            Some(self.first_mut())
+        }
+    }
+
+    impl<T> PeekBackMut<T> for NonemptyVec<T> {
+        fn peek_back_mut(&mut self) -> Option<&mut T> {
+            Some(self.last_mut())
+        }
+    }
+
+    impl<T> PullPeekFront<T> for NonemptyVec<T> {
+        fn pull_peek_front(&mut self) -> Option<&T> {
+            self.peek_front()
+        }
+    }
+
+    impl<T> PullPeekBack<T> for NonemptyVec<T> {
+        fn pull_peek_back(&mut self) -> Option<&T> {
+            self.peek_back()
+        }
+    }
+
+    impl<T> ExtendFront<T> for NonemptyVec<T> {
+        fn extend_front<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), Self::Err> {
+            let front: Vec<T> = iter.into_iter().collect();
+            if let Ok(front) = NonemptyVec::try_from(front) {
+                let old = mem::replace(self, front);
+                self.extend(old);
+            }
+            Ok(())
+        }
+    }
+
+    impl<T> ExtendBack<T> for NonemptyVec<T> {
+        fn extend_back<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), Self::Err> {
+            self.extend(iter);
+            Ok(())
+        }
+    }
+
+    impl<T> DrainFront<T> for NonemptyVec<T> {
+        type Drain<'a>
+            = vec::Drain<'a, T>
+        where
+            T: 'a;
+
+        fn drain_front(&mut self, n: usize) -> Self::Drain<'_> {
+            let n = n.min(self.len().get() - 1);
+            self.drain(..n).expect("at least one element is kept")
+        }
+    }
+
+    impl<T> DrainBack<T> for NonemptyVec<T> {
+        type Drain<'a>
+            = vec::Drain<'a, T>
+        where
+            T: 'a;
+
+        fn drain_back(&mut self, n: usize) -> Self::Drain<'_> {
+            let len = self.len().get();
+            let n = n.min(len - 1);
+            self.drain(len - n..).expect("at least one element is kept")
+        }
+    }
+
     // section: impl VecDeque
 
     impl<T> PushFront<T> for VecDeque<T> {
@@ -183,17 +391,80 @@ mod std_impls {
     }
 
     impl<T> PeekBack<T> for VecDeque<T> {
-        fn peek_back(&mut self) -> Option<&T> {
+        fn peek_back(&self) -> Option<&T> {
             self.back()
         }
     }
 
     impl<T> PeekFront<T> for VecDeque<T> {
-        fn peek_front(&mut self) -> Option<&T> {
+        fn peek_front(&self) -> Option<&T> {
             self.front()
         }
     }
 
+    impl<T> PeekFrontMut<T> for VecDeque<T> {
+        fn peek_front_mut(&mut self) -> Option<&mut T> {
+            self.front_mut()
+        }
+    }
+
+    impl<T> PeekBackMut<T> for VecDeque<T> {
+        fn peek_back_mut(&mut self) -> Option<&mut T> {
+            self.back_mut()
+        }
+    }
+
+    impl<T> PullPeekFront<T> for VecDeque<T> {
+        fn pull_peek_front(&mut self) -> Option<&T> {
+            self.peek_front()
+        }
+    }
+
+    impl<T> PullPeekBack<T> for VecDeque<T> {
+        fn pull_peek_back(&mut self) -> Option<&T> {
+            self.peek_back()
+        }
+    }
+
+    impl<T> ExtendFront<T> for VecDeque<T> {
+        fn extend_front<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), Self::Err> {
+            // adding to the back and rotating moves every element at most once
+            let len = self.len();
+            self.extend(iter);
+            self.rotate_right(self.len() - len);
+            Ok(())
+        }
+    }
+
+    impl<T> ExtendBack<T> for VecDeque<T> {
+        fn extend_back<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), Self::Err> {
+            self.extend(iter);
+            Ok(())
+        }
+    }
+
+    impl<T> DrainFront<T> for VecDeque<T> {
+        type Drain<'a>
+            = vec_deque::Drain<'a, T>
+        where
+            T: 'a;
+
+        fn drain_front(&mut self, n: usize) -> Self::Drain<'_> {
+            self.drain(..n.min(self.len()))
+        }
+    }
+
+    impl<T> DrainBack<T> for VecDeque<T> {
+        type Drain<'a>
+            = vec_deque::Drain<'a, T>
+        where
+            T: 'a;
+
+        fn drain_back(&mut self, n: usize) -> Self::Drain<'_> {
+            self.drain(self.len().saturating_sub(n)..)
+        }
+    }
+
     // section: impl LinkedList
 
     impl<T> PushFront<T> for LinkedList<T> {
@@ -227,16 +498,79 @@ mod std_impls {
     }
 
     impl<T> PeekFront<T> for LinkedList<T> {
-        fn peek_front(&mut self) -> Option<&T> {
+        fn peek_front(&self) -> Option<&T> {
             self.front()
         }
     }
 
     impl<T> PeekBack<T> for LinkedList<T> {
-        fn peek_back(&mut self) -> Option<&T> {
+        fn peek_back(&self) -> Option<&T> {
             self.back()
         }
     }
+
+    impl<T> PeekFrontMut<T> for LinkedList<T> {
+        fn peek_front_mut(&mut self) -> Option<&mut T> {
+            self.front_mut()
+        }
+    }
+
+    impl<T> PeekBackMut<T> for LinkedList<T> {
+        fn peek_back_mut(&mut self) -> Option<&mut T> {
+            self.back_mut()
+        }
+    }
+
+    impl<T> PullPeekFront<T> for LinkedList<T> {
+        fn pull_peek_front(&mut self) -> Option<&T> {
+            self.peek_front()
+        }
+    }
+
+    impl<T> PullPeekBack<T> for LinkedList<T> {
+        fn pull_peek_back(&mut self) -> Option<&T> {
+            self.peek_back()
+        }
+    }
+
+    impl<T> ExtendFront<T> for LinkedList<T> {
+        fn extend_front<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), Self::Err> {
+            let mut front: LinkedList<T> = iter.into_iter().collect();
+            front.append(self);
+            *self = front;
+            Ok(())
+        }
+    }
+
+    impl<T> ExtendBack<T> for LinkedList<T> {
+        fn extend_back<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), Self::Err> {
+            self.extend(iter);
+            Ok(())
+        }
+    }
+
+    impl<T> DrainFront<T> for LinkedList<T> {
+        type Drain<'a>
+            = linked_list::IntoIter<T>
+        where
+            T: 'a;
+
+        fn drain_front(&mut self, n: usize) -> Self::Drain<'_> {
+            let back = self.split_off(n.min(self.len()));
+            mem::replace(self, back).into_iter()
+        }
+    }
+
+    impl<T> DrainBack<T> for LinkedList<T> {
+        type Drain<'a>
+            = linked_list::IntoIter<T>
+        where
+            T: 'a;
+
+        fn drain_back(&mut self, n: usize) -> Self::Drain<'_> {
+            self.split_off(self.len().saturating_sub(n)).into_iter()
+        }
+    }
 }
 
 // section: impl Option
@@ -282,17 +616,75 @@ impl<T> PopBack<T> for Option<T> {
 }
 
 impl<T> PeekBack<T> for Option<T> {
-    fn peek_back(&mut self) -> Option<&T> {
+    fn peek_back(&self) -> Option<&T> {
         self.as_ref()
     }
 }
 
 impl<T> PeekFront<T> for Option<T> {
-    fn peek_front(&mut self) -> Option<&T> {
+    fn peek_front(&self) -> Option<&T> {
         self.as_ref()
     }
 }
 
+impl<T> PeekFrontMut<T> for Option<T> {
+    fn peek_front_mut(&mut self) -> Option<&mut T> {
+        self.as_mut()
+    }
+}
+
+impl<T> PeekBackMut<T> for Option<T> {
+    fn peek_back_mut(&mut self) -> Option<&mut T> {
+        self.as_mut()
+    }
+}
+
+impl<T> PullPeekFront<T> for Option<T> {
+    fn pull_peek_front(&mut self) -> Option<&T> {
+        self.peek_front()
+    }
+}
+
+impl<T> PullPeekBack<T> for Option<T> {
+    fn pull_peek_back(&mut self) -> Option<&T> {
+        self.peek_back()
+    }
+}
+
+impl<T> ExtendFront<T> for Option<T> {
+    fn extend_front<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), Self::Err> {
+        iter.into_iter().try_for_each(|t| self.push_front(t))
+    }
+}
+
+impl<T> ExtendBack<T> for Option<T> {
+    fn extend_back<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), Self::Err> {
+        iter.into_iter().try_for_each(|t| self.push_back(t))
+    }
+}
+
+impl<T> DrainFront<T> for Option<T> {
+    type Drain<'a>
+        = core::option::IntoIter<T>
+    where
+        T: 'a;
+
+    fn drain_front(&mut self, n: usize) -> Self::Drain<'_> {
+        if n == 0 { None } else { self.take() }.into_iter()
+    }
+}
+
+impl<T> DrainBack<T> for Option<T> {
+    type Drain<'a>
+        = core::option::IntoIter<T>
+    where
+        T: 'a;
+
+    fn drain_back(&mut self, n: usize) -> Self::Drain<'_> {
+        self.drain_front(n)
+    }
+}
+
 // section: impl ()
 
 impl<T> PushFront<T> for () {
@@ -324,13 +716,230 @@ impl<T> PopBack<T> for () {
 }
 
 impl<T> PeekBack<T> for () {
-    fn peek_back(&mut self) -> Option<&T> {
+    fn peek_back(&self) -> Option<&T> {
         None
     }
 }
 
 impl<T> PeekFront<T> for () {
-    fn peek_front(&mut self) -> Option<&T> {
+    fn peek_front(&self) -> Option<&T> {
+        None
+    }
+}
+
+impl<T> PeekFrontMut<T> for () {
+    fn peek_front_mut(&mut self) -> Option<&mut T> {
+        None
+    }
+}
+
+impl<T> PeekBackMut<T> for () {
+    fn peek_back_mut(&mut self) -> Option<&mut T> {
         None
     }
 }
+
+impl<T> PullPeekFront<T> for () {
+    fn pull_peek_front(&mut self) -> Option<&T> {
+        self.peek_front()
+    }
+}
+
+impl<T> PullPeekBack<T> for () {
+    fn pull_peek_back(&mut self) -> Option<&T> {
+        self.peek_back()
+    }
+}
+
+impl<T> ExtendFront<T> for () {
+    fn extend_front<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), Self::Err> {
+        iter.into_iter()
+            .try_for_each(|t| PushFront::push_front(self, t))
+    }
+}
+
+impl<T> ExtendBack<T> for () {
+    fn extend_back<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), Self::Err> {
+        iter.into_iter()
+            .try_for_each(|t| PushBack::push_back(self, t))
+    }
+}
+
+impl<T> DrainFront<T> for () {
+    type Drain<'a> = core::iter::Empty<T>;
+
+    fn drain_front(&mut self, _n: usize) -> Self::Drain<'_> {
+        core::iter::empty()
+    }
+}
+
+impl<T> DrainBack<T> for () {
+    type Drain<'a> = core::iter::Empty<T>;
+
+    fn drain_back(&mut self, _n: usize) -> Self::Drain<'_> {
+        core::iter::empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "use-std")]
+    #[test]
+    fn peek_vec() {
+        let mut v = vec![1, 2, 3];
+        assert_eq!((v.peek_front(), v.peek_back()), (Some(&1), Some(&3)));
+        *v.peek_front_mut().unwrap() = 10;
+        *v.peek_back_mut().unwrap() = 30;
+        assert_eq!(v.pull_peek_front(), Some(&10));
+        assert_eq!(v.pull_peek_back(), Some(&30));
+        let mut e = Vec::<u8>::new();
+        assert_eq!(e.peek_front(), None);
+        assert_eq!(e.peek_back_mut(), None);
+    }
+
+    #[cfg(feature = "use-std")]
+    #[test]
+    fn peek_vec_deque_linked_list() {
+        use std::collections::{LinkedList, VecDeque};
+
+        let mut d: VecDeque<_> = vec![1, 2].into_iter().collect();
+        *d.peek_front_mut().unwrap() += 10;
+        *d.peek_back_mut().unwrap() += 20;
+        assert_eq!((d.peek_front(), d.peek_back()), (Some(&11), Some(&22)));
+        assert_eq!(
+            (d.pull_peek_front().copied(), d.pull_peek_back().copied()),
+            (Some(11), Some(22))
+        );
+
+        let mut l: LinkedList<_> = vec![1, 2].into_iter().collect();
+        *l.peek_front_mut().unwrap() += 10;
+        *l.peek_back_mut().unwrap() += 20;
+        assert_eq!((l.peek_front(), l.peek_back()), (Some(&11), Some(&22)));
+        assert_eq!(
+            (l.pull_peek_front().copied(), l.pull_peek_back().copied()),
+            (Some(11), Some(22))
+        );
+    }
+
+    #[cfg(feature = "use-std")]
+    #[test]
+    fn peek_nonempty_vec() {
+        let mut v = nonempty_vec::nonempty_vec![1];
+        assert_eq!((v.peek_front(), v.peek_back()), (Some(&1), Some(&1)));
+        v.push(2);
+        *v.peek_front_mut().unwrap() = 10;
+        *v.peek_back_mut().unwrap() = 20;
+        assert_eq!(v.pull_peek_front(), Some(&10));
+        assert_eq!(v.pull_peek_back(), Some(&20));
+    }
+
+    #[test]
+    fn peek_option_unit() {
+        let mut o = Some(1);
+        *o.peek_front_mut().unwrap() = 2;
+        assert_eq!((o.peek_front(), o.peek_back()), (Some(&2), Some(&2)));
+        assert_eq!(o.pull_peek_back(), Some(&2));
+        assert_eq!(None::<u8>.peek_front(), None);
+
+        assert_eq!(PeekFront::<u8>::peek_front(&()), None);
+        assert_eq!(PeekBackMut::<u8>::peek_back_mut(&mut ()), None);
+        assert_eq!(PullPeekFront::<u8>::pull_peek_front(&mut ()), None);
+    }
+
+    #[cfg(feature = "use-std")]
+    fn collect<I: Iterator>(iter: I) -> Vec<I::Item> {
+        iter.collect()
+    }
+
+    #[cfg(feature = "use-std")]
+    #[test]
+    fn vec() {
+        let mut v = vec![3, 4];
+        assert_eq!(v.extend_front(vec![1, 2]), Ok(()));
+        assert_eq!(v.extend_back(vec![5]), Ok(()));
+        assert_eq!(v, [1, 2, 3, 4, 5]);
+        assert_eq!(collect(v.drain_front(2)), [1, 2]);
+        assert_eq!(collect(v.drain_back(2)), [4, 5]);
+        assert_eq!(collect(v.drain_back(usize::MAX)), [3]);
+        assert!(v.is_empty());
+        assert_eq!(collect(v.drain_front(1)), []);
+    }
+
+    #[cfg(feature = "use-std")]
+    #[test]
+    fn vec_deque() {
+        use std::collections::VecDeque;
+
+        // make the buffer wrap around before extending the front
+        let mut v = VecDeque::with_capacity(4);
+        v.extend([0, 0, 0, 4]);
+        v.drain(..3);
+        v.push_back(5);
+        // `VecDeque` has an unstable inherent `extend_front`
+        assert_eq!(ExtendFront::extend_front(&mut v, vec![1, 2, 3]), Ok(()));
+        assert_eq!(v, [1, 2, 3, 4, 5]);
+        assert_eq!(ExtendFront::extend_front(&mut v, None), Ok(()));
+        assert_eq!(v.extend_back(vec![6]), Ok(()));
+        assert_eq!(collect(v.drain_front(2)), [1, 2]);
+        assert_eq!(collect(v.drain_back(3)), [4, 5, 6]);
+        assert_eq!(collect(v.drain_front(usize::MAX)), [3]);
+        assert!(v.is_empty());
+    }
+
+    #[cfg(feature = "use-std")]
+    #[test]
+    fn linked_list() {
+        use std::collections::LinkedList;
+
+        let mut l: LinkedList<_> = vec![3, 4].into_iter().collect();
+        assert_eq!(l.extend_front(vec![1, 2]), Ok(()));
+        assert_eq!(l.extend_back(vec![5, 6]), Ok(()));
+        assert_eq!(collect(l.drain_front(2)), [1, 2]);
+        assert_eq!(collect(l.iter().copied()), [3, 4, 5, 6]);
+        assert_eq!(collect(l.drain_back(1)), [6]);
+        assert_eq!(collect(l.drain_front(0)), []);
+        assert_eq!(collect(l.drain_front(usize::MAX)), [3, 4, 5]);
+        assert!(l.is_empty());
+        assert_eq!(collect(l.drain_back(1)), []);
+    }
+
+    #[cfg(feature = "use-std")]
+    #[test]
+    fn nonempty_vec() {
+        let mut v = nonempty_vec::nonempty_vec![3];
+        assert_eq!(v.extend_front(vec![1, 2]), Ok(()));
+        assert_eq!(v.extend_front(None), Ok(()));
+        assert_eq!(v.extend_back(vec![4, 5]), Ok(()));
+        assert_eq!(*v.head(), 1);
+        assert_eq!(collect(v.drain_front(1)), [1]);
+        assert_eq!(collect(v.drain_back(2)), [4, 5]);
+        assert_eq!(collect(v.drain_front(usize::MAX)), [2]);
+        assert_eq!(v.clone().into_vec(), [3]);
+        assert_eq!(collect(v.drain_back(usize::MAX)), []);
+        assert_eq!(v.into_vec(), [3]);
+    }
+
+    #[test]
+    fn option() {
+        let mut o = None;
+        assert_eq!(o.extend_front([1, 2, 3]), Err(2));
+        assert_eq!(o, Some(1));
+        assert_eq!(o.extend_back([4]), Err(4));
+        assert_eq!(o.drain_back(0).next(), None);
+        assert_eq!(o.drain_front(1).next(), Some(1));
+        assert_eq!(o, None);
+        assert_eq!(o.extend_back([5]), Ok(()));
+        assert_eq!(o.drain_back(usize::MAX).next(), Some(5));
+    }
+
+    #[test]
+    fn unit() {
+        assert_eq!(().extend_front(None::<u8>), Ok(()));
+        assert_eq!(().extend_front([1, 2]), Err(1));
+        assert_eq!(().extend_back([3]), Err(3));
+        assert_eq!(DrainFront::<u8>::drain_front(&mut (), 1).next(), None);
+        assert_eq!(DrainBack::<u8>::drain_back(&mut (), 1).next(), None);
+    }
+}